@@ -0,0 +1,57 @@
+//! Where locale files are read from: the disk for the macros, anything else,
+//! such as memory, for tools and tests
+
+use std::{fs, io, path};
+
+/// Source of the locale files and everything read next to them
+pub trait FileSystem {
+    /// Paths of the entries of the directory `dir`
+    fn read_dir(&self, dir: &path::Path) -> io::Result<Vec<path::PathBuf>>;
+
+    /// Content of the file at `path`
+    fn read_file(&self, path: &path::Path) -> io::Result<Vec<u8>>;
+
+    /// Whether `path` names a file rather than a directory or nothing
+    fn is_file(&self, path: &path::Path) -> bool {
+        self.read_file(path).is_ok()
+    }
+
+    /// Path the generated code uses to `include!` the file at `path`, which
+    /// has to exist
+    fn canonicalize(&self, path: &path::Path) -> io::Result<path::PathBuf> {
+        self.read_file(path).map(|_| path.to_owned())
+    }
+}
+
+/// The disk, paths being relative to the working directory
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn read_dir(&self, dir: &path::Path) -> io::Result<Vec<path::PathBuf>> {
+        Ok(fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect())
+    }
+
+    fn read_file(&self, path: &path::Path) -> io::Result<Vec<u8>> {
+        let mut file = fs::File::open(path)?;
+
+        // A dev server rewriting the file waits until it has been read
+        #[cfg(feature = "file_locking")]
+        fs2::FileExt::lock_shared(&file)?;
+
+        let mut bytes = Vec::new();
+        io::Read::read_to_end(&mut file, &mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    fn is_file(&self, path: &path::Path) -> bool {
+        path.is_file()
+    }
+
+    fn canonicalize(&self, path: &path::Path) -> io::Result<path::PathBuf> {
+        fs::canonicalize(path)
+    }
+}
@@ -0,0 +1,30 @@
+//! Locale file parsing and code generation behind the
+//! [`anylang`](https://crates.io/crates/anylang) macros.
+//!
+//! The macros are thin wrappers around this crate, so the same constants can
+//! be generated outside of a proc-macro, e.g. from a `build.rs`:
+//!
+//! ```ignore
+//! let options = anylang_parse::Options::default();
+//! let file = anylang_parse::parse_from_file("lang/en_US.json".into(), &options)?;
+//! let code = proc_macro2::TokenStream::from(file).to_string();
+//!
+//! std::fs::write(format!("{}/lang.rs", std::env::var("OUT_DIR")?), code)?;
+//! ```
+
+#[macro_use]
+mod r#macro;
+pub mod file_system;
+pub mod options;
+pub mod parser;
+
+pub use file_system::{FileSystem, RealFileSystem};
+pub use options::Options;
+#[cfg(feature = "json")]
+pub use parser::{
+    Context, JsonNamespace, LocaleDiff, check_structure, fill_missing_keys, parse_json,
+    write_markdown_doc,
+};
+pub use parser::{File, TokenVariant, parse_from_file, parse_from_fs};
+
+pub(crate) const CRATE_NAME: &str = "anylang";
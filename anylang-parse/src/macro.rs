@@ -0,0 +1,5 @@
+macro_rules! error {
+    ($err:expr) => {
+        format!("[{}:ERROR] {}", crate::CRATE_NAME, $err)
+    };
+}
@@ -0,0 +1,579 @@
+use syn::{Ident, LitBool, LitInt, LitStr, Token, parse::Parse};
+
+/// Optional `key = value` settings that follow the language argument
+#[derive(Default, Clone)]
+pub struct Options {
+    /// Prefix every constant with the source file stem: `EN_US__PING`
+    pub append_file_name: bool,
+    /// Emit `<key>_plural(n)` helpers for `_zero`/`_one`/`_other` key groups
+    pub pluralize_suffix: bool,
+    /// Read a root array as `{"key": .., "value": ..}` pairs
+    pub kv_array_mode: bool,
+    /// Pack strings into one `static` buffer and emit `const fn` accessors
+    pub no_alloc: bool,
+    /// Emit a `Locale` trait with a method per key and a `StaticLocale` impl
+    pub generate_trait: bool,
+    /// Emit `#[wasm_bindgen]` getters for `wasm32` targets
+    pub wasm_export: bool,
+    /// What a JSON `null` turns into
+    pub null_behavior: NullBehavior,
+    /// Rewrites every string value before it is emitted
+    pub transform: Option<Transform>,
+    /// Case of constant names, plain uppercase of the key when unset
+    pub name_transform: Option<NameTransform>,
+    /// Emit a `#[test]` per string constant asserting it is not empty
+    pub generate_tests: bool,
+    /// Emit `pub enum LangKeys` with a variant per key
+    pub generate_enum: bool,
+    /// Emit string constants as `&CStr` from `c"..."` literals
+    pub cstring_mode: bool,
+    /// Emit `<KEY>_PTR: *const u8` and `<KEY>_LEN: usize` next to every
+    /// string constant
+    pub const_ptr: bool,
+    /// Wrap every array constant in a `<Key>Array` newtype
+    pub const_len_array: bool,
+    /// Leave `'static` out of the types of the constants
+    pub suppress_type_annotations: bool,
+    /// Lowercase the names of the modules generated for objects
+    pub lowercase_module_names: bool,
+    /// Attributes put on the generated module, given without `#[]`
+    pub module_attrs: Vec<syn::Attribute>,
+    /// Visibility of the generated modules and constants, `pub` when unset
+    pub macro_visibility: Option<syn::Visibility>,
+    /// Strip HTML tags from every string value
+    pub sanitize_html: bool,
+    /// HTML-escape every string value
+    pub escape_html: bool,
+    /// Reject arrays mixing strings, numbers, booleans and nulls
+    pub strict_typed_arrays: bool,
+    /// JSON file, next to the locale, mapping old keys to their new paths
+    pub compat_aliases: Option<String>,
+    /// Reject strings with fewer characters
+    pub min_string_len: Option<usize>,
+    /// Cut longer strings to this many characters, ending with `…`
+    pub truncate_at: Option<usize>,
+    /// Define string constants as indices into one `static POOL` array
+    pub string_interning_mode: bool,
+    /// Point constants repeating an earlier value at that constant
+    pub deduplicate_strings: bool,
+    /// Deprecate keys that `<file>.versions.json` lists as added after this
+    /// version
+    pub deprecate_since: Option<String>,
+    /// Depend on `$OUT_DIR/anylang_timestamp` so every build expands again
+    pub force_rerun: bool,
+    /// Ignore `//` comment lines before the JSON
+    pub skip_leading_comments: bool,
+    /// Write every top-level module to its own file in `$OUT_DIR`
+    pub segment_by_namespace: bool,
+    /// List the files read in `$OUT_DIR/anylang_inputs.txt`
+    pub watch: bool,
+    /// Tell the format of a file with an unknown or missing extension from
+    /// its content
+    pub auto_detect: bool,
+    /// Fall back to shorter language tags when there is no file for the tag
+    pub locale_negotiation: bool,
+    /// Locale whose values fill keys missing from the requested one
+    pub fallback: Option<String>,
+    /// Locale whose values fill keys missing from both the requested one and
+    /// `fallback`
+    pub final_fallback: Option<String>,
+    /// Fill keys only other JSON locales of the directory have with their path
+    pub fallback_to_key: bool,
+    /// Fill keys only other locales of the directory have with an empty
+    /// string, warning about each
+    pub partial: bool,
+    /// Require every other JSON locale of the directory to have the keys and
+    /// value shapes of the included one
+    pub strict_structure: bool,
+    /// File extensions in order of preference when several files match
+    pub locale_priority: Vec<String>,
+    /// Top-level key holding the metadata of the locale instead of strings
+    pub locale_metadata_key: Option<String>,
+    /// Locale codes standing for the file of another code
+    pub locale_alias: Vec<(String, String)>,
+    /// Key paths the locale has to contain
+    pub required_keys: Vec<String>,
+    /// Reject a language argument that is not a BCP 47 tag
+    pub validate_bcp47: bool,
+    /// File holding the hash of the generated code, warn when it changes
+    pub stable_hash: Option<String>,
+    /// Key paths whose values must be email addresses
+    pub validate_email_fields: Vec<String>,
+    /// Warn about strings longer than this many characters
+    pub warn_string_len: Option<usize>,
+    /// Reject keys longer than this many characters
+    pub key_length_limit: Option<usize>,
+    /// Fail on objects nested deeper than this many modules
+    pub max_nesting_depth: Option<usize>,
+    /// Reject strings ending with whitespace
+    pub validate_no_trailing_spaces: bool,
+    /// Warn about keys with non-ASCII characters
+    pub warn_non_ascii_keys: bool,
+    /// Where to write a JSON Schema of the locale structure
+    pub json_schema_export: Option<String>,
+    /// Where to write every key and value as CSV
+    pub csv_report: Option<String>,
+    /// Where to write a gettext `.pot` template keyed by path
+    pub gettext_pot: Option<String>,
+    /// Where to write a gettext `.pot` template keyed by the source strings
+    pub pot_file: Option<String>,
+    /// Where to write TypeScript declarations of the generated constants
+    pub typescript_types: Option<String>,
+    /// Where to write an Xcode `.strings` file
+    pub xcode_strings: Option<String>,
+    /// Where to write an Android `strings.xml` resource file
+    pub android_xml: Option<String>,
+    /// Where to write a Swift enum mirroring the generated modules
+    pub swift_enum: Option<String>,
+    /// Where to write a Kotlin object mirroring the generated modules
+    pub kotlin_object: Option<String>,
+    /// Where to write a Markdown table of the keys in every locale
+    pub markdown_doc: Option<String>,
+    /// Where to write a C header with a `#define` per constant
+    pub c_header: Option<String>,
+    /// Where to write a Lua table mirroring the locale
+    pub lua_table: Option<String>,
+    /// Where to write a Python dict mirroring the locale
+    pub python_dict: Option<String>,
+    /// Where to write the parsed locale back as JSON
+    pub generate_rust_json: Option<String>,
+    /// Where to write a GraphQL type with a field per constant
+    pub graphql_schema: Option<String>,
+    /// How the locale files are encoded on disk
+    pub encoding: Encoding,
+    /// What to do with a key repeated within one JSON object
+    pub key_collision_strategy: KeyCollisionStrategy,
+    /// Normalize the values of the keys listed in `<file>.normalization.json`
+    /// to their Unicode forms
+    #[cfg(feature = "normalization")]
+    pub normalization_file: bool,
+    /// Verify every file against the SHA-256 in `<file>.sha256`
+    #[cfg(feature = "checksum")]
+    pub checksum_file: bool,
+    /// Only keep the keys whose dotted path matches
+    #[cfg(feature = "regex")]
+    pub key_filter_regex: Option<regex::Regex>,
+    /// Drop the keys whose dotted path matches
+    #[cfg(feature = "regex")]
+    pub key_exclude_regex: Option<regex::Regex>,
+    /// Emit a `<KEY>_TRANSLITERATED` ASCII copy of every constant
+    #[cfg(feature = "transliterate")]
+    pub transliterate: bool,
+    /// Where to write the parsed locale as RON
+    #[cfg(feature = "ron")]
+    pub ron_output: Option<String>,
+    /// Warn about mixed-direction strings of right-to-left locales lacking
+    /// directional marks
+    #[cfg(feature = "bidi")]
+    pub verify_rtl_markers: bool,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum NullBehavior {
+    /// An empty string
+    #[default]
+    Empty,
+    /// A compile error
+    Error,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    /// Plain JSON text
+    #[default]
+    Utf8,
+    /// Base64 of the JSON text
+    #[cfg(feature = "base64")]
+    Base64,
+}
+
+#[derive(Default, Clone, Copy, PartialEq)]
+pub enum KeyCollisionStrategy {
+    /// A compile error
+    #[default]
+    Error,
+    /// The first value is kept
+    FirstWins,
+    /// The last value is kept, as serde_json does
+    LastWins,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Transform {
+    Uppercase,
+    Lowercase,
+    Trim,
+    /// Capitalizes the first letter of every word
+    TitleCase,
+}
+
+/// Constant naming schemes; kebab variants use `__` since identifiers cannot
+/// contain `-`
+#[derive(Clone, Copy, PartialEq)]
+pub enum NameTransform {
+    ScreamingSnake,
+    ScreamingKebab,
+    LowerSnake,
+    LowerCamel,
+    UpperCamel,
+    Kebab,
+}
+
+impl NameTransform {
+    pub fn is_upper_case(self) -> bool {
+        matches!(self, Self::ScreamingSnake | Self::ScreamingKebab)
+    }
+}
+
+impl Options {
+    /// `locale_priority`, preferring JSON when it is not set
+    pub fn locale_priority(&self) -> Vec<String> {
+        if self.locale_priority.is_empty() {
+            vec!["json".to_owned()]
+        } else {
+            self.locale_priority.clone()
+        }
+    }
+}
+
+impl Parse for Options {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        while !input.is_empty() {
+            let _comma: Token![,] = input.parse()?;
+
+            if input.is_empty() {
+                break;
+            }
+
+            let key = input.parse::<Ident>()?;
+            let _eq: Token![=] = input.parse()?;
+
+            match key.to_string().as_str() {
+                "append_file_name" => options.append_file_name = input.parse::<LitBool>()?.value,
+                "pluralize_suffix" => options.pluralize_suffix = input.parse::<LitBool>()?.value,
+                "kv_array_mode" => options.kv_array_mode = input.parse::<LitBool>()?.value,
+                "no_alloc" => options.no_alloc = input.parse::<LitBool>()?.value,
+                "generate_trait" => options.generate_trait = input.parse::<LitBool>()?.value,
+                "wasm_export" => options.wasm_export = input.parse::<LitBool>()?.value,
+                #[cfg(feature = "checksum")]
+                "checksum_file" => options.checksum_file = input.parse::<LitBool>()?.value,
+                #[cfg(feature = "regex")]
+                "key_filter_regex" => options.key_filter_regex = Some(parse_regex(input)?),
+                #[cfg(feature = "regex")]
+                "key_exclude_regex" => options.key_exclude_regex = Some(parse_regex(input)?),
+                #[cfg(feature = "ron")]
+                "ron_output" => options.ron_output = Some(input.parse::<LitStr>()?.value()),
+                #[cfg(feature = "bidi")]
+                "verify_rtl_markers" => {
+                    options.verify_rtl_markers = input.parse::<LitBool>()?.value
+                },
+                #[cfg(feature = "transliterate")]
+                "transliterate" => options.transliterate = input.parse::<LitBool>()?.value,
+                #[cfg(feature = "normalization")]
+                "normalization_file" => {
+                    options.normalization_file = input.parse::<LitBool>()?.value
+                },
+                "compat_aliases" => options.compat_aliases = Some(input.parse::<LitStr>()?.value()),
+                "deprecate_since" => {
+                    options.deprecate_since = Some(input.parse::<LitStr>()?.value())
+                },
+                "deduplicate_strings" => {
+                    options.deduplicate_strings = input.parse::<LitBool>()?.value
+                },
+                "const_len_array" => options.const_len_array = input.parse::<LitBool>()?.value,
+                "suppress_type_annotations" => {
+                    options.suppress_type_annotations = input.parse::<LitBool>()?.value
+                },
+                "lowercase_module_names" => {
+                    options.lowercase_module_names = input.parse::<LitBool>()?.value
+                },
+                "module_attrs" => options.module_attrs = parse_attr_list(input)?,
+                "macro_visibility" => {
+                    let value = input.parse::<LitStr>()?;
+                    let visibility = match value.value().as_str() {
+                        "private" => Ok(syn::Visibility::Inherited),
+                        other => syn::parse_str(other),
+                    };
+
+                    options.macro_visibility = Some(visibility.map_err(|e| {
+                        syn::Error::new_spanned(
+                            &value,
+                            error!(format!(
+                                "Invalid macro_visibility {} cause {e}, expected \"pub\", \
+                                 \"pub(crate)\", \"pub(super)\" or \"private\"",
+                                value.value()
+                            )),
+                        )
+                    })?);
+                },
+                "cstring_mode" => options.cstring_mode = input.parse::<LitBool>()?.value,
+                "const_ptr" => options.const_ptr = input.parse::<LitBool>()?.value,
+                "sanitize_html" => options.sanitize_html = input.parse::<LitBool>()?.value,
+                "escape_html" => options.escape_html = input.parse::<LitBool>()?.value,
+                "force_rerun" => options.force_rerun = input.parse::<LitBool>()?.value,
+                "watch" => options.watch = input.parse::<LitBool>()?.value,
+                "auto_detect" => options.auto_detect = input.parse::<LitBool>()?.value,
+                "segment_by_namespace" => {
+                    options.segment_by_namespace = input.parse::<LitBool>()?.value
+                },
+                "skip_leading_comments" => {
+                    options.skip_leading_comments = input.parse::<LitBool>()?.value
+                },
+                "generate_enum" => options.generate_enum = input.parse::<LitBool>()?.value,
+                "generate_tests" => options.generate_tests = input.parse::<LitBool>()?.value,
+                "validate_no_trailing_spaces" => {
+                    options.validate_no_trailing_spaces = input.parse::<LitBool>()?.value
+                },
+                "warn_non_ascii_keys" => {
+                    options.warn_non_ascii_keys = input.parse::<LitBool>()?.value
+                },
+                "validate_bcp47" => options.validate_bcp47 = input.parse::<LitBool>()?.value,
+                "required_keys" => options.required_keys = parse_str_list(input)?,
+                "validate_email_fields" => options.validate_email_fields = parse_str_list(input)?,
+                "stable_hash" => options.stable_hash = Some(input.parse::<LitStr>()?.value()),
+                "string_interning_mode" => {
+                    options.string_interning_mode = input.parse::<LitBool>()?.value
+                },
+                "strict_typed_arrays" => {
+                    options.strict_typed_arrays = input.parse::<LitBool>()?.value
+                },
+                "csv_report" => options.csv_report = Some(input.parse::<LitStr>()?.value()),
+                "gettext_pot" => options.gettext_pot = Some(input.parse::<LitStr>()?.value()),
+                "pot_file" => options.pot_file = Some(input.parse::<LitStr>()?.value()),
+                "json_schema_export" => {
+                    options.json_schema_export = Some(input.parse::<LitStr>()?.value())
+                },
+                "locale_priority" => options.locale_priority = parse_str_list(input)?,
+                "locale_alias" => options.locale_alias = parse_str_map(input)?,
+                "locale_metadata_key" => {
+                    options.locale_metadata_key = Some(input.parse::<LitStr>()?.value())
+                },
+                "locale_negotiation" => {
+                    options.locale_negotiation = input.parse::<LitBool>()?.value
+                },
+                "fallback" => options.fallback = Some(input.parse::<LitStr>()?.value()),
+                "final_fallback" => options.final_fallback = Some(input.parse::<LitStr>()?.value()),
+                "fallback_to_key" => options.fallback_to_key = input.parse::<LitBool>()?.value,
+                "partial" => options.partial = input.parse::<LitBool>()?.value,
+                "strict_structure" => options.strict_structure = input.parse::<LitBool>()?.value,
+                "warn_string_len" => {
+                    options.warn_string_len = Some(input.parse::<LitInt>()?.base10_parse()?)
+                },
+                "max_nesting_depth" => {
+                    options.max_nesting_depth = Some(input.parse::<LitInt>()?.base10_parse()?)
+                },
+                "key_length_limit" => {
+                    options.key_length_limit = Some(input.parse::<LitInt>()?.base10_parse()?)
+                },
+                "min_string_len" => {
+                    options.min_string_len = Some(input.parse::<LitInt>()?.base10_parse()?)
+                },
+                "xcode_strings" => options.xcode_strings = Some(input.parse::<LitStr>()?.value()),
+                "android_xml" => options.android_xml = Some(input.parse::<LitStr>()?.value()),
+                "swift_enum" => options.swift_enum = Some(input.parse::<LitStr>()?.value()),
+                "kotlin_object" => options.kotlin_object = Some(input.parse::<LitStr>()?.value()),
+                "markdown_doc" => options.markdown_doc = Some(input.parse::<LitStr>()?.value()),
+                "c_header" => options.c_header = Some(input.parse::<LitStr>()?.value()),
+                "lua_table" => options.lua_table = Some(input.parse::<LitStr>()?.value()),
+                "python_dict" => options.python_dict = Some(input.parse::<LitStr>()?.value()),
+                "generate_rust_json" => {
+                    options.generate_rust_json = Some(input.parse::<LitStr>()?.value())
+                },
+                "graphql_schema" => options.graphql_schema = Some(input.parse::<LitStr>()?.value()),
+                "typescript_types" => {
+                    options.typescript_types = Some(input.parse::<LitStr>()?.value())
+                },
+                "truncate_at" => {
+                    let value = input.parse::<LitInt>()?;
+
+                    // Even the ellipsis would not fit
+                    if value.base10_parse::<usize>()? == 0 {
+                        return Err(syn::Error::new_spanned(
+                            &value,
+                            error!("truncate_at must be at least 1"),
+                        ));
+                    }
+
+                    options.truncate_at = Some(value.base10_parse()?)
+                },
+                "null_behavior" => {
+                    let value = input.parse::<LitStr>()?;
+
+                    options.null_behavior = match value.value().as_str() {
+                        "empty" => NullBehavior::Empty,
+                        "error" => NullBehavior::Error,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                error!(format!(
+                                    "Unknown null_behavior {other}, expected \"empty\" or \
+                                     \"error\""
+                                )),
+                            ));
+                        },
+                    };
+                },
+                "name_transform" => {
+                    let value = input.parse::<LitStr>()?;
+
+                    options.name_transform = Some(match value.value().as_str() {
+                        "screaming_snake" => NameTransform::ScreamingSnake,
+                        "screaming_kebab" => NameTransform::ScreamingKebab,
+                        "lower_snake" => NameTransform::LowerSnake,
+                        "lower_camel" => NameTransform::LowerCamel,
+                        "upper_camel" => NameTransform::UpperCamel,
+                        "kebab" => NameTransform::Kebab,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                error!(format!(
+                                    "Unknown name_transform {other}, expected \
+                                     \"screaming_snake\", \"screaming_kebab\", \"lower_snake\", \
+                                     \"lower_camel\", \"upper_camel\" or \"kebab\""
+                                )),
+                            ));
+                        },
+                    });
+                },
+                "encoding" => {
+                    let value = input.parse::<LitStr>()?;
+
+                    options.encoding = match value.value().as_str() {
+                        "utf8" => Encoding::Utf8,
+                        #[cfg(feature = "base64")]
+                        "base64" => Encoding::Base64,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                error!(format!(
+                                    "Unknown encoding {other}, expected \"utf8\" or \"base64\" \
+                                     (with the base64 feature)"
+                                )),
+                            ));
+                        },
+                    };
+                },
+                "key_collision_strategy" => {
+                    let value = input.parse::<LitStr>()?;
+
+                    options.key_collision_strategy = match value.value().as_str() {
+                        "error" => KeyCollisionStrategy::Error,
+                        "first_wins" => KeyCollisionStrategy::FirstWins,
+                        "last_wins" => KeyCollisionStrategy::LastWins,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                error!(format!(
+                                    "Unknown key_collision_strategy {other}, expected \"error\", \
+                                     \"first_wins\" or \"last_wins\""
+                                )),
+                            ));
+                        },
+                    };
+                },
+                "transform" => {
+                    let value = input.parse::<LitStr>()?;
+
+                    options.transform = Some(match value.value().as_str() {
+                        "uppercase" => Transform::Uppercase,
+                        "lowercase" => Transform::Lowercase,
+                        "trim" => Transform::Trim,
+                        "title_case" => Transform::TitleCase,
+                        other => {
+                            return Err(syn::Error::new_spanned(
+                                &value,
+                                error!(format!(
+                                    "Unknown transform {other}, expected \"uppercase\", \
+                                     \"lowercase\", \"trim\" or \"title_case\""
+                                )),
+                            ));
+                        },
+                    });
+                },
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        &key,
+                        error!(format!("Unknown option {key}")),
+                    ));
+                },
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+/// A string literal compiled as a regular expression
+#[cfg(feature = "regex")]
+fn parse_regex(input: syn::parse::ParseStream) -> syn::Result<regex::Regex> {
+    let value = input.parse::<LitStr>()?;
+
+    regex::Regex::new(&value.value()).map_err(|e| {
+        syn::Error::new_spanned(
+            &value,
+            error!(format!("Invalid regex {} cause {e}", value.value())),
+        )
+    })
+}
+
+/// `["allow(dead_code)", "doc = \"Strings\""]` as outer attributes
+fn parse_attr_list(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::Attribute>> {
+    use syn::parse::Parser;
+
+    let content;
+
+    syn::bracketed!(content in input);
+
+    let mut attrs = Vec::new();
+
+    for attr in content.parse_terminated::<LitStr, Token![,]>(|input| input.parse())? {
+        let parsed = syn::Attribute::parse_outer
+            .parse_str(&format!("#[{}]", attr.value()))
+            .map_err(|e| {
+                syn::Error::new_spanned(
+                    &attr,
+                    error!(format!(
+                        "Invalid module attribute {} cause {e}",
+                        attr.value()
+                    )),
+                )
+            })?;
+
+        attrs.extend(parsed);
+    }
+
+    Ok(attrs)
+}
+
+/// `{"a": "b", "c": "d"}`
+fn parse_str_map(input: syn::parse::ParseStream) -> syn::Result<Vec<(String, String)>> {
+    let content;
+
+    syn::braced!(content in input);
+
+    Ok(content
+        .parse_terminated::<(LitStr, LitStr), Token![,]>(|input| {
+            let key = input.parse::<LitStr>()?;
+            let _colon: Token![:] = input.parse()?;
+
+            Ok((key, input.parse::<LitStr>()?))
+        })?
+        .iter()
+        .map(|(key, value)| (key.value(), value.value()))
+        .collect())
+}
+
+/// `["a", "b"]`
+fn parse_str_list(input: syn::parse::ParseStream) -> syn::Result<Vec<String>> {
+    let content;
+
+    syn::bracketed!(content in input);
+
+    Ok(content
+        .parse_terminated::<LitStr, Token![,]>(|input| input.parse())?
+        .iter()
+        .map(LitStr::value)
+        .collect())
+}
@@ -0,0 +1,2207 @@
+mod export;
+
+use quote::quote;
+use std::collections::HashMap;
+
+use super::*;
+
+pub(super) use export::{
+    check_stable_hash, write_all, write_inputs, write_markdown, write_segment,
+};
+
+#[cfg(feature = "json")]
+#[derive(PartialEq)]
+pub(super) enum TokenJson {
+    Namespace(JsonNamespace),
+    Token(Token),
+}
+
+#[cfg(feature = "json")]
+impl TokenJson {
+    fn name(&self) -> &str {
+        match self {
+            Self::Namespace(namespace) => namespace.namespace.as_deref().unwrap_or_default(),
+            Self::Token(token) => &token.name,
+        }
+    }
+
+    fn cfg(&self) -> Option<&Cfg> {
+        match self {
+            Self::Namespace(namespace) => namespace.cfg.as_ref(),
+            Self::Token(token) => token.cfg.as_ref(),
+        }
+    }
+
+    fn cfg_mut(&mut self) -> &mut Option<Cfg> {
+        match self {
+            Self::Namespace(namespace) => &mut namespace.cfg,
+            Self::Token(token) => &mut token.cfg,
+        }
+    }
+
+    /// Whether the item exists on every target, which is what generated
+    /// helpers referring to it need
+    fn is_unconditional(&self) -> bool {
+        !matches!(self.cfg(), Some(Cfg::Only(_)))
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<Token> for TokenJson {
+    fn from(value: Token) -> Self {
+        Self::Token(value)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<JsonNamespace> for TokenJson {
+    fn from(value: JsonNamespace) -> Self {
+        Self::Namespace(value)
+    }
+}
+
+#[cfg(feature = "json")]
+impl TokenJson {
+    fn into_tokens(self, codegen: &mut Codegen) -> proc_macro2::TokenStream {
+        let cfg = self.cfg().map(Cfg::to_attr);
+        let deprecated = match &self {
+            Self::Token(Token {
+                since: Some(since), ..
+            }) => Some(quote! { #[deprecated(since = #since)] }),
+            _ => None,
+        };
+        let allow = codegen
+            .options
+            .name_transform
+            .is_some_and(|transform| !transform.is_upper_case())
+            .then(|| quote! { #[allow(non_upper_case_globals)] });
+        let vis = codegen.visibility(false);
+        // Pointer and length pairs for C FFI, valid for the whole program as
+        // they point into a string literal
+        let pointers = match &self {
+            Self::Token(
+                token @ Token {
+                    data: TokenVariant::Single(_),
+                    ..
+                },
+            ) if codegen.options.const_ptr => {
+                let value = accessor(&[], token, codegen.options);
+                let value = if codegen.options.cstring_mode && !codegen.options.no_alloc {
+                    quote! { #value.to_bytes() }
+                } else {
+                    value
+                };
+                let [ptr, len] = ["ptr", "len"].map(|suffix| {
+                    syn::Ident::new(
+                        &const_name(&format!("{}_{suffix}", token.name), codegen.options),
+                        proc_macro2::Span::call_site(),
+                    )
+                });
+
+                Some(quote! {
+                    #cfg
+                    #deprecated
+                    #allow
+                    #vis const #ptr: *const u8 = #value.as_ptr();
+                    #cfg
+                    #deprecated
+                    #allow
+                    #vis const #len: usize = #value.len();
+                })
+            },
+            _ => None,
+        };
+        // ASCII copies for tools that cannot handle other scripts
+        #[cfg(feature = "transliterate")]
+        let transliterated = match &self {
+            Self::Token(token) if codegen.options.transliterate => {
+                let name = syn::Ident::new(
+                    &const_name(&format!("{}_transliterated", token.name), codegen.options),
+                    proc_macro2::Span::call_site(),
+                );
+                let data = match &token.data {
+                    TokenVariant::Single(s) => TokenVariant::Single(deunicode::deunicode(s)),
+                    TokenVariant::Array(arr) => {
+                        TokenVariant::Array(arr.iter().map(|s| deunicode::deunicode(s)).collect())
+                    },
+                };
+                let ty = data.get_type(codegen.options);
+                let value = data.into_data(codegen.options);
+
+                Some(quote! {
+                    #cfg
+                    #deprecated
+                    #allow
+                    #vis const #name: #ty = #value;
+                })
+            },
+            _ => None,
+        };
+        #[cfg(not(feature = "transliterate"))]
+        let transliterated = None::<proc_macro2::TokenStream>;
+        let item = match self {
+            Self::Namespace(namespace) => {
+                let conditional = codegen.conditional;
+
+                codegen.conditional |= namespace.cfg.is_some();
+                let item = namespace.into_tokens(codegen);
+                codegen.conditional = conditional;
+
+                item
+            },
+            Self::Token(Token {
+                name,
+                data: TokenVariant::Single(s),
+                ..
+            }) if codegen.options.no_alloc => {
+                let name = syn::Ident::new(&name.to_lowercase(), proc_macro2::Span::call_site());
+                let data = codegen.root_path("LOCALE_DATA");
+                let offset = codegen.data.len();
+                let len = s.len();
+
+                codegen.data.extend_from_slice(s.as_bytes());
+                codegen.data.push(0);
+
+                quote! {
+                    #vis const fn #name() -> &'static str {
+                        match ::core::str::from_utf8(#data.split_at(#offset).1.split_at(#len).0) {
+                            Ok(s) => s,
+                            Err(_) => unreachable!(),
+                        }
+                    }
+                }
+            },
+            Self::Token(token) => {
+                let name = syn::Ident::new(
+                    &const_name(&token.name, codegen.options),
+                    proc_macro2::Span::call_site(),
+                );
+                let ty = token.data.get_type(codegen.options);
+                let shared = match &token.data {
+                    TokenVariant::Single(s) if codegen.options.deduplicate_strings => {
+                        codegen.shared_string(s, &name, token.cfg.is_none())
+                    },
+                    _ => None,
+                };
+                let value = match (shared, token.data) {
+                    (Some(shared), _) => shared,
+                    (None, TokenVariant::Single(s))
+                        if codegen.options.string_interning_mode
+                            && !codegen.options.cstring_mode =>
+                    {
+                        codegen.interned(&s)
+                    },
+                    (None, data) => data.into_data(codegen.options),
+                };
+
+                match ty {
+                    syn::Type::Array(array) if codegen.options.const_len_array => {
+                        let wrapper = syn::Ident::new(
+                            &array_type_name(&token.name),
+                            proc_macro2::Span::call_site(),
+                        );
+
+                        // The constant comes first to receive the `#[cfg]` and
+                        // `#[deprecated]` of the key
+                        quote! {
+                            #allow
+                            #vis const #name: #wrapper = #wrapper(#value);
+
+                            #cfg
+                            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+                            pub struct #wrapper(pub #array);
+
+                            #cfg
+                            impl ::core::ops::Deref for #wrapper {
+                                type Target = #array;
+
+                                fn deref(&self) -> &Self::Target {
+                                    &self.0
+                                }
+                            }
+                        }
+                    },
+                    ty => {
+                        quote! {
+                            #allow
+                            #vis const #name: #ty = #value;
+                        }
+                    },
+                }
+            },
+        };
+
+        quote! {
+            #cfg
+            #deprecated
+            #item
+            #pointers
+            #transliterated
+        }
+    }
+}
+
+/// State shared while turning a namespace tree into tokens
+#[cfg(feature = "json")]
+struct Codegen<'a> {
+    options: &'a Options,
+    /// Stem of the file the tree was parsed from
+    file_name: &'a str,
+    /// How many modules deep the item being generated is
+    depth: usize,
+    /// NUL-terminated strings packed into `LOCALE_DATA` in `no_alloc` mode
+    data: Vec<u8>,
+    /// Modules between the root module and the one being generated
+    modules: Vec<String>,
+    /// Whether the module being generated is behind a `#[cfg]`
+    conditional: bool,
+    /// Path from the root module to the first constant holding each string in
+    /// `deduplicate_strings` mode
+    strings: HashMap<String, Vec<String>>,
+    /// Every distinct string in `string_interning_mode`, emitted as `POOL`
+    pool: Vec<String>,
+}
+
+#[cfg(feature = "json")]
+impl<'a> Codegen<'a> {
+    fn new(options: &'a Options, file_name: &'a str) -> Self {
+        Self {
+            options,
+            file_name,
+            depth: 0,
+            data: Vec::new(),
+            modules: Vec::new(),
+            conditional: false,
+            strings: HashMap::new(),
+            pool: Vec::new(),
+        }
+    }
+
+    /// Path to a constant already holding `value`, remembering `name` as that
+    /// constant if there is none. Only constants present on every target are
+    /// shared
+    fn shared_string(
+        &mut self,
+        value: &str,
+        name: &syn::Ident,
+        unconditional: bool,
+    ) -> Option<proc_macro2::TokenStream> {
+        if let Some(path) = self.strings.get(value) {
+            let supers = (1..self.depth).map(|_| quote! { super:: });
+            let path = path
+                .iter()
+                .map(|segment| syn::Ident::new(segment, proc_macro2::Span::call_site()));
+
+            return Some(quote! { #(#supers)* #(#path)::* });
+        }
+
+        if unconditional && !self.conditional {
+            let path = self
+                .modules
+                .iter()
+                .cloned()
+                .chain([name.to_string()])
+                .collect();
+
+            self.strings.insert(value.to_owned(), path);
+        }
+
+        None
+    }
+
+    /// Visibility of a generated module or constant, `root` meaning the root
+    /// module. Restricted visibilities are relative to the module they are
+    /// written in, so only the root module gets them as given and the items
+    /// inside it `pub(crate)`, still limited to wherever the root is visible
+    fn visibility(&self, root: bool) -> proc_macro2::TokenStream {
+        match &self.options.macro_visibility {
+            None | Some(syn::Visibility::Public(_)) => quote! { pub },
+            Some(visibility) if root => quote! { #visibility },
+            Some(_) => quote! { pub(crate) },
+        }
+    }
+
+    /// Path to an item of the root module from the current module
+    fn root_path(&self, item: &str) -> proc_macro2::TokenStream {
+        let supers = (1..self.depth).map(|_| quote! { super:: });
+        let item = syn::Ident::new(item, proc_macro2::Span::call_site());
+
+        quote! { #(#supers)* #item }
+    }
+
+    /// `POOL[i]` holding `value` in `string_interning_mode`, adding it to the
+    /// pool when it is new
+    fn interned(&mut self, value: &str) -> proc_macro2::TokenStream {
+        let index = match self.pool.iter().position(|pooled| pooled == value) {
+            Some(index) => index,
+            None => {
+                self.pool.push(value.to_owned());
+                self.pool.len() - 1
+            },
+        };
+        let pool = self.root_path("POOL");
+
+        quote! { #pool[#index] }
+    }
+}
+
+#[cfg(feature = "json")]
+#[derive(Default, PartialEq)]
+pub struct JsonNamespace {
+    namespace: Option<String>,
+    tokens: Vec<TokenJson>,
+    /// Deprecated `(old, new)` constant names from `compat_aliases`
+    aliases: Vec<(String, String)>,
+    cfg: Option<Cfg>,
+    /// `_context` annotation of the object, shown on the module
+    context: Option<String>,
+    /// Constants of the `meta` module read from the `.info.json` sidecar
+    meta: Vec<(String, String)>,
+}
+
+#[cfg(feature = "json")]
+impl JsonNamespace {
+    fn new<T>(namespace: T) -> Self
+    where
+        String: From<T>,
+    {
+        let namespace = String::from(namespace);
+
+        Self {
+            namespace: Some(namespace),
+            ..Default::default()
+        }
+    }
+
+    /// Orders the whole tree by name to keep the output independent of the
+    /// order keys appear in the file
+    pub(super) fn sort(&mut self) {
+        self.tokens.sort_by(|a, b| a.name().cmp(b.name()));
+
+        for token in &mut self.tokens {
+            if let TokenJson::Namespace(namespace) = token {
+                namespace.sort();
+            }
+        }
+    }
+
+    /// Nested namespace at the dotted `modules` path, `self` when it is empty
+    fn namespace_mut(&mut self, modules: &str) -> Option<&mut Self> {
+        let mut namespace = self;
+
+        for module in modules.split('.').filter(|module| !module.is_empty()) {
+            namespace = namespace.tokens.iter_mut().find_map(|token| {
+                match token {
+                    TokenJson::Namespace(namespace)
+                        if namespace.namespace.as_deref() == Some(module) =>
+                    {
+                        Some(namespace)
+                    },
+                    _ => None,
+                }
+            })?;
+        }
+
+        Some(namespace)
+    }
+
+    /// Every token, one per `_cfg_` variant, at the dotted key `path`
+    fn tokens_mut(&mut self, path: &str, ctx: &Context) -> Vec<&mut Token> {
+        let (modules, key) = path.rsplit_once('.').unwrap_or(("", path));
+        let name = token_name(key, ctx);
+
+        self.namespace_mut(modules)
+            .map(|namespace| {
+                namespace
+                    .tokens
+                    .iter_mut()
+                    .filter_map(|token| {
+                        match token {
+                            TokenJson::Token(token) if token.name == name => Some(token),
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Moves the keys of `other` that `self` lacks into it, with every `_cfg_`
+    /// variant of them
+    pub(super) fn merge_missing(&mut self, other: JsonNamespace) {
+        let names = self
+            .tokens
+            .iter()
+            .map(|token| token.name().to_owned())
+            .collect::<std::collections::HashSet<_>>();
+
+        for token in other.tokens {
+            match token {
+                TokenJson::Namespace(other) if other.cfg.is_none() => {
+                    let existing = self.tokens.iter_mut().find_map(|token| {
+                        match token {
+                            TokenJson::Namespace(namespace)
+                                if namespace.cfg.is_none()
+                                    && namespace.namespace == other.namespace =>
+                            {
+                                Some(namespace)
+                            },
+                            _ => None,
+                        }
+                    });
+
+                    match existing {
+                        Some(namespace) => namespace.merge_missing(other),
+                        None if !names.contains(other.namespace.as_deref().unwrap_or_default()) => {
+                            self.tokens.push(other.into());
+                        },
+                        None => {},
+                    }
+                },
+                token if !names.contains(token.name()) => self.tokens.push(token),
+                _ => {},
+            }
+        }
+    }
+
+    /// Adds the constants of `other` missing from `self`, unconditionally and
+    /// with their dotted key path as the value under `fallback_to_key`, an
+    /// empty string otherwise, keeping the shape of arrays. The key paths added
+    /// go to `filled`
+    pub(super) fn fill_missing(
+        &mut self,
+        other: &JsonNamespace,
+        path: &mut Vec<String>,
+        ctx: &Context,
+        filled: &mut Vec<String>,
+    ) {
+        for token in other.tokens.iter().filter(|token| token.is_unconditional()) {
+            match token {
+                TokenJson::Namespace(other) => {
+                    let name = other.namespace.clone().unwrap_or_default();
+                    let position = self.tokens.iter().position(|token| {
+                        matches!(token, TokenJson::Namespace(namespace)
+                            if namespace.cfg.is_none()
+                                && namespace.namespace.as_deref() == Some(name.as_str()))
+                    });
+                    let position = position.unwrap_or_else(|| {
+                        self.tokens.push(JsonNamespace::new(name.as_str()).into());
+                        self.tokens.len() - 1
+                    });
+                    let TokenJson::Namespace(namespace) = &mut self.tokens[position] else {
+                        unreachable!("position points at a namespace");
+                    };
+
+                    path.push(name);
+                    namespace.fill_missing(other, path, ctx, filled);
+                    path.pop();
+                },
+                TokenJson::Token(other) => {
+                    let name = token_name(&other.name, ctx);
+
+                    if self.tokens.iter().any(|token| token.name() == name) {
+                        continue;
+                    }
+
+                    let key = path
+                        .iter()
+                        .map(String::as_str)
+                        .chain([other.name.as_str()])
+                        .collect::<Vec<_>>()
+                        .join(".");
+                    let value = if ctx.options.fallback_to_key {
+                        key.clone()
+                    } else {
+                        String::new()
+                    };
+                    let data = match &other.data {
+                        TokenVariant::Single(_) => TokenVariant::Single(value),
+                        TokenVariant::Array(arr) => TokenVariant::Array(vec![value; arr.len()]),
+                    };
+
+                    filled.push(key);
+
+                    self.tokens.push(TokenJson::Token(Token {
+                        name,
+                        data,
+                        cfg: None,
+                        since: None,
+                    }));
+                },
+            }
+        }
+    }
+
+    /// Calls `f` for every constant in the tree with the module path leading to
+    /// it
+    fn for_each_token<'a>(
+        &'a self,
+        path: &mut Vec<&'a str>,
+        f: &mut impl FnMut(&[&str], &'a Token),
+    ) {
+        for token in self.tokens.iter().filter(|token| token.is_unconditional()) {
+            match token {
+                TokenJson::Namespace(namespace) => {
+                    path.push(namespace.namespace.as_deref().unwrap_or_default());
+                    namespace.for_each_token(path, f);
+                    path.pop();
+                },
+                TokenJson::Token(token) => f(path, token),
+            }
+        }
+    }
+}
+
+/// Key paths that differ between two parses of a locale
+#[cfg(feature = "json")]
+#[derive(Default)]
+pub struct LocaleDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Present in both with a different value or shape
+    pub changed: Vec<String>,
+}
+
+#[cfg(feature = "json")]
+impl LocaleDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonNamespace {
+    /// Compares the keys present on every target, each list sorted by path,
+    /// e.g. to report what changed between two versions of a locale
+    pub fn diff(old: &JsonNamespace, new: &JsonNamespace) -> LocaleDiff {
+        Self::compare(old, new, |old, new| old == new)
+    }
+
+    /// Like [`JsonNamespace::diff`] but only a string turning into an array or
+    /// back counts as a change, translated values are expected to differ
+    pub(super) fn structure_diff(old: &JsonNamespace, new: &JsonNamespace) -> LocaleDiff {
+        Self::compare(old, new, |old, new| {
+            std::mem::discriminant(old) == std::mem::discriminant(new)
+        })
+    }
+
+    fn compare(
+        old: &JsonNamespace,
+        new: &JsonNamespace,
+        same: impl Fn(&TokenVariant, &TokenVariant) -> bool,
+    ) -> LocaleDiff {
+        fn flatten(namespace: &JsonNamespace) -> std::collections::BTreeMap<String, &TokenVariant> {
+            let mut keys = std::collections::BTreeMap::new();
+
+            namespace.for_each_token(&mut Vec::new(), &mut |path, token| {
+                let path = path.iter().copied().chain([token.name.as_str()]);
+
+                keys.insert(path.collect::<Vec<_>>().join("."), &token.data);
+            });
+
+            keys
+        }
+
+        let (old, new) = (flatten(old), flatten(new));
+        let mut diff = LocaleDiff::default();
+
+        for (path, data) in &new {
+            match old.get(path) {
+                None => diff.added.push(path.clone()),
+                Some(old) if !same(old, data) => diff.changed.push(path.clone()),
+                Some(_) => {},
+            }
+        }
+
+        diff.removed = old
+            .into_keys()
+            .filter(|path| !new.contains_key(path))
+            .collect();
+
+        diff
+    }
+}
+
+#[cfg(feature = "json")]
+impl JsonNamespace {
+    fn into_tokens(mut self, codegen: &mut Codegen) -> proc_macro2::TokenStream {
+        // Again for the keys `fill_missing_keys` added after parsing
+        if codegen.depth == 0 {
+            self.sort();
+        }
+
+        let mod_vis = codegen.visibility(codegen.depth == 0);
+        let vis = codegen.visibility(false);
+        let locale_trait = (codegen.depth == 0 && codegen.options.generate_trait)
+            .then(|| locale_trait(&self, codegen.options));
+        let wasm = (codegen.depth == 0 && codegen.options.wasm_export)
+            .then(|| wasm_getters(&self, codegen.options));
+        let keys_enum =
+            (codegen.depth == 0 && codegen.options.generate_enum).then(|| keys_enum(&self));
+        let tests = (codegen.depth == 0 && codegen.options.generate_tests)
+            .then(|| non_empty_tests(&self, codegen));
+        let mod_name = syn::Ident::new(
+            &module_name(self.namespace.as_deref().unwrap_or("lang"), codegen.options),
+            proc_macro2::Span::call_site(),
+        );
+        let plurals = if codegen.options.pluralize_suffix {
+            plural_helpers(&self.tokens, codegen.options)
+        } else {
+            Vec::new()
+        };
+
+        let aliases = self
+            .aliases
+            .iter()
+            .filter_map(|(old, new)| {
+                let target = self.tokens.iter().find_map(|token| {
+                    match token {
+                        TokenJson::Token(token) if token.name == *new => Some(token),
+                        _ => None,
+                    }
+                })?;
+                let value = accessor(&[], target, codegen.options);
+                let note = format!("renamed to {value}");
+
+                Some(
+                    if codegen.options.no_alloc && matches!(target.data, TokenVariant::Single(_)) {
+                        let name =
+                            syn::Ident::new(&old.to_lowercase(), proc_macro2::Span::call_site());
+
+                        quote! {
+                            #[deprecated(note = #note)]
+                            #vis const fn #name() -> &'static str { #value }
+                        }
+                    } else {
+                        let name = syn::Ident::new(
+                            &const_name(old, codegen.options),
+                            proc_macro2::Span::call_site(),
+                        );
+                        let ty = target.data.get_type(codegen.options);
+
+                        quote! {
+                            #[deprecated(note = #note)]
+                            #vis const #name: #ty = #value;
+                        }
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let nested = codegen.depth > 0;
+
+        if nested {
+            codegen.modules.push(mod_name.to_string());
+        }
+
+        let segment = codegen.depth == 0 && codegen.options.segment_by_namespace;
+        let lang = codegen.file_name.replace(['.', '-'], "_").to_lowercase();
+        let mut segments = HashMap::<String, usize>::new();
+
+        codegen.depth += 1;
+        let mods_and_consts = self
+            .tokens
+            .into_iter()
+            .map(|token| {
+                let file = match &token {
+                    TokenJson::Namespace(namespace) if segment => {
+                        let name = namespace.namespace.as_deref().unwrap_or_default();
+                        // `_cfg_` blocks may define the same module twice
+                        let count = segments.entry(name.to_owned()).or_default();
+                        let file = match *count {
+                            0 => format!("lang_{lang}_{name}.rs"),
+                            n => format!("lang_{lang}_{name}_{n}.rs"),
+                        };
+
+                        *count += 1;
+                        Some(file)
+                    },
+                    _ => None,
+                };
+                let item = token.into_tokens(codegen);
+
+                match file {
+                    Some(file) => write_segment(&file, item),
+                    None => item,
+                }
+            })
+            .collect::<Vec<_>>();
+        codegen.depth -= 1;
+
+        if nested {
+            codegen.modules.pop();
+        }
+
+        let data = (codegen.depth == 0 && codegen.options.no_alloc).then(|| {
+            let len = codegen.data.len();
+            let bytes = proc_macro2::Literal::byte_string(&codegen.data);
+
+            quote! { static LOCALE_DATA: [u8; #len] = *#bytes; }
+        });
+        let pool = (codegen.depth == 0 && codegen.options.string_interning_mode).then(|| {
+            let len = codegen.pool.len();
+            let strings = &codegen.pool;
+
+            quote! { static POOL: [&str; #len] = [#(#strings),*]; }
+        });
+
+        let doc = self
+            .context
+            .map(|context| format!("Context: {context}"))
+            .map(|context| quote! { #[doc = #context] });
+
+        // The file written by the user's build script changes on every build, so
+        // rustc has to expand the macro again
+        let rerun = (codegen.depth == 0 && codegen.options.force_rerun).then(|| {
+            quote! {
+                const _: &str = include_str!(concat!(env!("OUT_DIR"), "/anylang_timestamp"));
+            }
+        });
+        let meta = (!self.meta.is_empty()).then(|| {
+            let consts = self.meta.iter().map(|(name, value)| {
+                let name = syn::Ident::new(name, proc_macro2::Span::call_site());
+
+                quote! { #vis const #name: &str = #value; }
+            });
+
+            quote! {
+                #vis mod meta {
+                    #(#consts)*
+                }
+            }
+        });
+        // Generated helpers refer to keys deprecated by `deprecate_since`
+        let allow = (codegen.depth == 0 && codegen.options.deprecate_since.is_some())
+            .then(|| quote! { #[allow(deprecated)] });
+        let attrs = (codegen.depth == 0).then_some(&codegen.options.module_attrs);
+        let attrs = attrs.into_iter().flatten();
+
+        quote! {
+            #doc
+            #allow
+            #(#attrs)*
+            #mod_vis mod #mod_name {
+                #(#mods_and_consts)*
+                #meta
+                #(#aliases)*
+                #(#plurals)*
+                #data
+                #pool
+                #rerun
+                #locale_trait
+                #wasm
+                #keys_enum
+                #tests
+            }
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<File> for proc_macro2::TokenStream {
+    fn from(val: File) -> Self {
+        if let Some(include) = val.include {
+            return quote! { include!(#include); };
+        }
+
+        let tokens = val
+            .tokens
+            .into_tokens(&mut Codegen::new(&val.options, &val.name));
+        let warnings = val.warnings.iter().map(|message| warning(message));
+        let hash = val.options.stable_hash.as_deref().map(|path| {
+            match check_stable_hash(path, &tokens) {
+                Ok(changed) => changed.as_deref().map(warning).unwrap_or_default(),
+                Err(e) => e.into_compile_error(),
+            }
+        });
+
+        quote! {
+            #tokens
+            #(#warnings)*
+            #hash
+        }
+    }
+}
+
+/// Path to a generated item, relative to the root module
+#[cfg(feature = "json")]
+fn accessor(path: &[&str], token: &Token, options: &Options) -> proc_macro2::TokenStream {
+    let mods = path.iter().map(|module| {
+        syn::Ident::new(
+            &module_name(module, options),
+            proc_macro2::Span::call_site(),
+        )
+    });
+
+    if options.no_alloc && matches!(token.data, TokenVariant::Single(_)) {
+        let name = syn::Ident::new(&token.name.to_lowercase(), proc_macro2::Span::call_site());
+        quote! { #(#mods::)* #name() }
+    } else {
+        let name = syn::Ident::new(
+            &const_name(&token.name, options),
+            proc_macro2::Span::call_site(),
+        );
+        let inner = (options.const_len_array && matches!(token.data, TokenVariant::Array(_)))
+            .then(|| quote! { .0 });
+        quote! { #(#mods::)* #name #inner }
+    }
+}
+
+/// Name of the module generated for the object at `key`
+#[cfg(feature = "json")]
+fn module_name(key: &str, options: &Options) -> String {
+    if options.lowercase_module_names {
+        key.to_lowercase()
+    } else {
+        key.to_owned()
+    }
+}
+
+/// `value` read as `&str`, converting the `&CStr` of a string constant in
+/// `cstring_mode`
+#[cfg(feature = "json")]
+fn as_str(
+    value: proc_macro2::TokenStream,
+    token: &Token,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    if options.cstring_mode && !options.no_alloc && matches!(token.data, TokenVariant::Single(_)) {
+        // The bytes came from a Rust string, so they are always UTF-8
+        quote! {
+            match #value.to_str() {
+                Ok(s) => s,
+                Err(_) => unreachable!(),
+            }
+        }
+    } else {
+        value
+    }
+}
+
+/// Lowercased key path joined with underscores: `dummy_foo`
+#[cfg(feature = "json")]
+fn flat_name(path: &[&str], token: &Token) -> String {
+    path.iter()
+        .copied()
+        .chain([token.name.as_str()])
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// Builds `pub mod wasm` with a `#[wasm_bindgen]` getter per constant, compiled
+/// only for `wasm32` targets
+#[cfg(feature = "json")]
+fn wasm_getters(root: &JsonNamespace, options: &Options) -> proc_macro2::TokenStream {
+    let mut getters = Vec::new();
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let name = syn::Ident::new(&flat_name(path, token), proc_macro2::Span::call_site());
+        let value = accessor(path, token, options);
+
+        getters.push(match token.data {
+            TokenVariant::Single(_) => {
+                let value = as_str(quote! { super::#value }, token, options);
+
+                quote! {
+                    #[::wasm_bindgen::prelude::wasm_bindgen]
+                    pub fn #name() -> ::std::string::String {
+                        ::std::borrow::ToOwned::to_owned(#value)
+                    }
+                }
+            },
+            TokenVariant::Array(_) => {
+                quote! {
+                    #[::wasm_bindgen::prelude::wasm_bindgen]
+                    pub fn #name() -> ::std::vec::Vec<::std::string::String> {
+                        super::#value.iter().map(|s| ::std::borrow::ToOwned::to_owned(*s)).collect()
+                    }
+                }
+            },
+        });
+    });
+
+    quote! {
+        #[cfg(target_arch = "wasm32")]
+        pub mod wasm {
+            #(#getters)*
+        }
+    }
+}
+
+/// Builds `pub enum LangKeys` with a variant per constant
+#[cfg(feature = "json")]
+fn keys_enum(root: &JsonNamespace) -> proc_macro2::TokenStream {
+    let mut variants = Vec::new();
+    let mut keys = Vec::new();
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let segments = path.iter().copied().chain([token.name.as_str()]);
+
+        variants.push(syn::Ident::new(
+            &segments
+                .clone()
+                .flat_map(split_words)
+                .map(|word| capitalize(&word))
+                .collect::<String>(),
+            proc_macro2::Span::call_site(),
+        ));
+        keys.push(segments.collect::<Vec<_>>().join("."));
+    });
+
+    quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum LangKeys {
+            #(#variants,)*
+        }
+
+        impl LangKeys {
+            /// Dotted path of the key in the locale file
+            pub const fn as_str(&self) -> &'static str {
+                match *self {
+                    #(Self::#variants => #keys,)*
+                }
+            }
+
+            /// Every key, sorted by path
+            pub const fn all() -> &'static [LangKeys] {
+                &[#(Self::#variants,)*]
+            }
+        }
+    }
+}
+
+/// Builds a `#[cfg(test)]` module asserting every string constant is not
+/// empty
+#[cfg(feature = "json")]
+fn non_empty_tests(root: &JsonNamespace, codegen: &Codegen) -> proc_macro2::TokenStream {
+    let lang = codegen.file_name.replace('.', "_").to_lowercase();
+    let mut tests = Vec::new();
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        if let TokenVariant::Single(_) = token.data {
+            let name = syn::Ident::new(
+                &format!("test_{lang}_{}_non_empty", flat_name(path, token)),
+                proc_macro2::Span::call_site(),
+            );
+            let value = accessor(path, token, codegen.options);
+
+            tests.push(quote! {
+                #[test]
+                fn #name() {
+                    assert!(!super::#value.is_empty());
+                }
+            });
+        }
+    });
+
+    quote! {
+        #[cfg(test)]
+        mod tests {
+            #(#tests)*
+        }
+    }
+}
+
+/// Builds `pub trait Locale` with a method per constant and its `StaticLocale`
+/// implementation returning the generated values
+#[cfg(feature = "json")]
+fn locale_trait(root: &JsonNamespace, options: &Options) -> proc_macro2::TokenStream {
+    let mut signatures = Vec::new();
+    let mut methods = Vec::new();
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let name = syn::Ident::new(&flat_name(path, token), proc_macro2::Span::call_site());
+        let value = as_str(accessor(path, token, options), token, options);
+
+        match token.data {
+            TokenVariant::Single(_) => {
+                signatures.push(quote! { fn #name(&self) -> &str; });
+                methods.push(quote! { fn #name(&self) -> &str { #value } });
+            },
+            TokenVariant::Array(_) => {
+                signatures.push(quote! { fn #name(&self) -> &[&str]; });
+                methods.push(quote! { fn #name(&self) -> &[&str] { &#value } });
+            },
+        }
+    });
+
+    quote! {
+        pub trait Locale {
+            #(#signatures)*
+        }
+
+        pub struct StaticLocale;
+
+        impl Locale for StaticLocale {
+            #(#methods)*
+        }
+    }
+}
+
+/// CLDR plural categories recognised as key suffixes
+#[cfg(feature = "json")]
+const PLURAL_SUFFIXES: [&str; 6] = ["_zero", "_one", "_two", "_few", "_many", "_other"];
+
+/// Builds `fn <key>_plural(n: u64) -> &'static str` for every group of sibling
+/// string constants sharing a base key with an `_other` form, following
+/// English plural rules
+#[cfg(feature = "json")]
+fn plural_helpers(tokens: &[TokenJson], options: &Options) -> Vec<proc_macro2::TokenStream> {
+    let mut groups = std::collections::BTreeMap::<&str, Vec<(&str, &Token)>>::new();
+
+    for token in tokens.iter().filter(|token| token.is_unconditional()) {
+        if let TokenJson::Token(
+            token @ Token {
+                data: TokenVariant::Single(_),
+                ..
+            },
+        ) = token
+        {
+            for suffix in PLURAL_SUFFIXES {
+                if let Some(base) = token.name.strip_suffix(suffix) {
+                    groups.entry(base).or_default().push((&suffix[1..], token));
+                }
+            }
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter_map(|(base, forms)| {
+            let form = |category: &str| {
+                forms
+                    .iter()
+                    .find(|(c, _)| *c == category)
+                    .map(|(_, token)| as_str(accessor(&[], token, options), token, options))
+            };
+            let other = form("other")?;
+            let zero = form("zero").map(|zero| quote! { 0 => #zero, });
+            let one = form("one").map(|one| quote! { 1 => #one, });
+            let fn_name = syn::Ident::new(
+                &format!("{}_plural", base.to_lowercase()),
+                proc_macro2::Span::call_site(),
+            );
+
+            Some(quote! {
+                pub const fn #fn_name(n: u64) -> &'static str {
+                    match n {
+                        #zero
+                        #one
+                        _ => #other,
+                    }
+                }
+            })
+        })
+        .collect()
+}
+
+/// What `parse_json` needs to know about the file being parsed
+#[cfg(feature = "json")]
+pub struct Context<'a> {
+    /// Uppercased file stem, the name given to a root value
+    pub(super) name: String,
+    /// File name as found on disk, for error messages
+    pub(super) source: String,
+    pub(super) options: &'a Options,
+    /// Keys of the objects enclosing the value being parsed
+    pub(super) path: Vec<String>,
+    /// Compile warnings collected while parsing
+    pub(super) warnings: Vec<String>,
+}
+
+#[cfg(feature = "json")]
+impl<'a> Context<'a> {
+    /// Context for the root of the file `file_name` (its stem), `source` being
+    /// the file name shown in error messages
+    pub fn new(file_name: &str, source: String, options: &'a Options) -> Self {
+        Self {
+            name: file_name.replace('.', "_").to_uppercase(),
+            source,
+            options,
+            path: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Compile warnings collected so far
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Dotted path of `key` within the file: `dummy.foo`
+    fn key_path(&self, key: &str) -> String {
+        self.path
+            .iter()
+            .map(String::as_str)
+            .chain([key])
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Whether the key filters let the constant for `key` through
+    #[cfg(feature = "regex")]
+    fn keeps_key(&self, key: &str) -> bool {
+        let path = self.key_path(key);
+
+        self.options
+            .key_filter_regex
+            .as_ref()
+            .is_none_or(|filter| filter.is_match(&path))
+            && self
+                .options
+                .key_exclude_regex
+                .as_ref()
+                .is_none_or(|exclude| !exclude.is_match(&path))
+    }
+
+    /// Whether any key filter is set
+    #[cfg(feature = "regex")]
+    fn is_filtered(&self) -> bool {
+        self.options.key_filter_regex.is_some() || self.options.key_exclude_regex.is_some()
+    }
+}
+
+#[cfg(feature = "json")]
+pub fn parse_json(
+    value: &serde_json::Value,
+    root: &mut JsonNamespace,
+    ctx: &mut Context,
+) -> syn::Result<()> {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut cfg_blocks = Vec::new();
+
+            for (key, val) in map {
+                if let Some(condition) = key.strip_prefix("_cfg_") {
+                    cfg_blocks.push((key, condition, val));
+                    continue;
+                }
+
+                if key == "_context" {
+                    let serde_json::Value::String(context) = val else {
+                        return Err(syn::Error::new_spanned(
+                            syn::LitStr::new(&val.to_string(), proc_macro2::Span::call_site()),
+                            error!(format!(
+                                "Expected String under {} in {}, but actually {val}",
+                                ctx.key_path(key),
+                                ctx.source
+                            )),
+                        ));
+                    };
+
+                    root.context = Some(context.to_owned());
+                    continue;
+                }
+
+                let len = key.chars().count();
+
+                if let Some(limit) = ctx.options.key_length_limit
+                    && len > limit
+                {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(key, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Key '{}' ({len} chars) in {} exceeds key_length_limit ({limit})",
+                            ctx.key_path(key),
+                            ctx.source
+                        )),
+                    ));
+                }
+
+                if ctx.options.warn_non_ascii_keys && !key.is_ascii() {
+                    let warning = format!(
+                        "Key '{}' in {} is not ASCII, which many tools handle poorly in \
+                         identifiers; consider an ASCII key",
+                        ctx.key_path(key),
+                        ctx.source
+                    );
+
+                    ctx.warnings.push(warning);
+                }
+
+                if let serde_json::Value::Object(_) = val {
+                    if let Some(limit) = ctx.options.max_nesting_depth
+                        && ctx.path.len() >= limit
+                    {
+                        return Err(syn::Error::new_spanned(
+                            syn::LitStr::new(key, proc_macro2::Span::call_site()),
+                            error!(format!(
+                                "Key path '{}' in {} exceeds max_nesting_depth ({limit})",
+                                ctx.key_path(key),
+                                ctx.source
+                            )),
+                        ));
+                    }
+
+                    let mut namespace = JsonNamespace::new(key);
+
+                    ctx.path.push(key.to_owned());
+                    parse_json(val, &mut namespace, ctx)?;
+                    ctx.path.pop();
+
+                    // Objects left without keys by a filter would be empty
+                    // modules
+                    #[cfg(feature = "regex")]
+                    if namespace.tokens.is_empty() && ctx.is_filtered() {
+                        continue;
+                    }
+
+                    root.tokens.push(TokenJson::from(namespace));
+                    continue;
+                }
+
+                #[cfg(feature = "regex")]
+                if !ctx.keeps_key(key) {
+                    continue;
+                }
+
+                root.tokens
+                    .push(TokenJson::from(token(token_name(key, ctx), key, val, ctx)?));
+            }
+
+            for (key, condition, val) in cfg_blocks {
+                let Some(predicate) = cfg_predicate(condition).filter(|_| val.is_object()) else {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(key, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Expected Object under {key} in {} with a condition such as \
+                             _cfg_target_os_ios or _cfg_unix",
+                            ctx.source
+                        )),
+                    ));
+                };
+                let mut block = JsonNamespace::default();
+
+                parse_json(val, &mut block, ctx)?;
+
+                for mut token in block.tokens {
+                    for fallback in root.tokens.iter_mut().filter(|fallback| {
+                        fallback.name() == token.name() && fallback.is_unconditional()
+                    }) {
+                        match fallback.cfg_mut() {
+                            Some(Cfg::Not(predicates)) => predicates.push(predicate.clone()),
+                            cfg => *cfg = Some(Cfg::Not(vec![predicate.clone()])),
+                        }
+                    }
+
+                    *token.cfg_mut() = Some(Cfg::Only(predicate.clone()));
+                    root.tokens.push(token);
+                }
+            }
+        },
+        serde_json::Value::Array(arr) if ctx.options.kv_array_mode => {
+            let mut map = serde_json::Map::with_capacity(arr.len());
+
+            for val in arr {
+                match (val.get("key"), val.get("value")) {
+                    (Some(serde_json::Value::String(key)), Some(value)) => {
+                        map.insert(key.to_owned(), value.to_owned());
+                    },
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            syn::LitStr::new(&val.to_string(), proc_macro2::Span::call_site()),
+                            format!(
+                                "Expected {{\"key\": .., \"value\": ..}} into Array, but actually \
+                                 {val}"
+                            ),
+                        ));
+                    },
+                }
+            }
+
+            parse_json(&serde_json::Value::Object(map), root, ctx)?;
+        },
+        serde_json::Value::Array(arr) => {
+            for val in arr {
+                if matches!(val, serde_json::Value::Object(_)) {
+                    parse_json(val, root, ctx)?;
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&val.to_string(), proc_macro2::Span::call_site()),
+                        format!("Excepted Object into Array, but actually {val:?}"),
+                    ));
+                }
+            }
+        },
+        _ => {
+            let name = ctx.name.clone();
+
+            root.tokens
+                .push(TokenJson::from(token(name.clone(), &name, value, ctx)?));
+        },
+    }
+
+    Ok(())
+}
+
+/// Replaces every `"$include": "<path>"` key with the keys of the JSON, TOML or
+/// YAML file at `<path>`, relative to `dir`; keys already present in the
+/// object win
+#[cfg(feature = "json")]
+pub(super) fn resolve_includes(
+    files: &dyn FileSystem,
+    value: &mut serde_json::Value,
+    dir: &path::Path,
+    stack: &mut Vec<path::PathBuf>,
+    inputs: &mut Vec<path::PathBuf>,
+    options: &Options,
+) -> syn::Result<()> {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(include) = map.remove("$include") {
+                let serde_json::Value::String(include) = include else {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&include.to_string(), proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Expected file path in $include, but actually {include}"
+                        )),
+                    ));
+                };
+                let file = dir.join(&include);
+
+                if stack.contains(&file) {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&include, proc_macro2::Span::call_site()),
+                        error!(format!("Cyclic $include of {}", file.display())),
+                    ));
+                }
+
+                let data = files.read_file(&file).map_err(|e| {
+                    syn::Error::new_spanned(
+                        syn::LitStr::new(&include, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Cannot read included file {} cause {e}",
+                            file.display()
+                        )),
+                    )
+                })?;
+                let mut included =
+                    decode(&data, &file, file.extension().unwrap_or_default(), options)?;
+
+                inputs.push(file.clone());
+                stack.push(file.clone());
+                resolve_includes(
+                    files,
+                    &mut included,
+                    file.parent().unwrap_or(path::Path::new("")),
+                    stack,
+                    inputs,
+                    options,
+                )?;
+                stack.pop();
+
+                let serde_json::Value::Object(included) = included else {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&include, proc_macro2::Span::call_site()),
+                        error!(format!("Expected Object in {}", file.display())),
+                    ));
+                };
+
+                for (key, val) in included {
+                    map.entry(key).or_insert(val);
+                }
+            }
+
+            for val in map.values_mut() {
+                resolve_includes(files, val, dir, stack, inputs, options)?;
+            }
+        },
+        serde_json::Value::Array(arr) => {
+            for val in arr {
+                resolve_includes(files, val, dir, stack, inputs, options)?;
+            }
+        },
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Reads a `{"old_key": "new.key.path"}` file and registers a deprecated
+/// `old_key` constant next to every `new.key.path` one
+#[cfg(feature = "json")]
+pub(super) fn load_compat_aliases(
+    files: &dyn FileSystem,
+    root: &mut JsonNamespace,
+    file: &path::Path,
+    ctx: &Context,
+) -> syn::Result<()> {
+    let aliases = read_sidecar(files, file, "compat")?;
+
+    for (old, new) in aliases {
+        let missing = || {
+            syn::Error::new_spanned(
+                syn::LitStr::new(&new, proc_macro2::Span::call_site()),
+                error!(format!("Compat alias {old} points to missing key {new}")),
+            )
+        };
+        let (modules, key) = new.rsplit_once('.').unwrap_or(("", &new));
+        let namespace = root.namespace_mut(modules).ok_or_else(missing)?;
+        let name = token_name(key, ctx);
+
+        if !namespace
+            .tokens
+            .iter()
+            .any(|token| matches!(token, TokenJson::Token(token) if token.name == name))
+        {
+            return Err(missing());
+        }
+
+        namespace.aliases.push((token_name(&old, ctx), name));
+    }
+
+    Ok(())
+}
+
+/// Reads the `{"language": "English", "direction": "ltr"}` metadata of a
+/// locale into `meta` constants, `direction` becoming `TEXT_DIRECTION`
+#[cfg(feature = "json")]
+pub(super) fn load_info(
+    files: &dyn FileSystem,
+    root: &mut JsonNamespace,
+    file: &path::Path,
+) -> syn::Result<()> {
+    for (key, value) in read_sidecar(files, file, "info")? {
+        add_meta(root, &key, value, file)?;
+    }
+
+    Ok(())
+}
+
+/// Removes the `locale_metadata_key` object from the top level of a locale,
+/// returning its `key: value` pairs
+#[cfg(feature = "json")]
+pub(super) fn take_metadata(
+    value: &mut serde_json::Value,
+    options: &Options,
+    file: &path::Path,
+) -> syn::Result<Vec<(String, String)>> {
+    let (Some(key), serde_json::Value::Object(map)) = (&options.locale_metadata_key, value) else {
+        return Ok(Vec::new());
+    };
+    let Some(metadata) = map.remove(key) else {
+        return Ok(Vec::new());
+    };
+    let serde_json::Value::Object(metadata) = metadata else {
+        return Err(syn::Error::new_spanned(
+            syn::LitStr::new(key, proc_macro2::Span::call_site()),
+            error!(format!(
+                "Expected Object under {key} in {}, but actually {metadata}",
+                file.display()
+            )),
+        ));
+    };
+
+    metadata
+        .into_iter()
+        .map(|(name, value)| {
+            match value {
+                serde_json::Value::String(value) => Ok((name, value)),
+                value => {
+                    Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&name, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Expected String under {key}.{name} in {}, but actually {value}",
+                            file.display()
+                        )),
+                    ))
+                },
+            }
+        })
+        .collect()
+}
+
+/// Registers a `meta` constant, replacing an earlier one of the same name
+#[cfg(feature = "json")]
+pub(super) fn add_meta(
+    root: &mut JsonNamespace,
+    key: &str,
+    value: String,
+    file: &path::Path,
+) -> syn::Result<()> {
+    let name = match key {
+        "direction" => "TEXT_DIRECTION".to_owned(),
+        key => key.to_uppercase(),
+    };
+
+    if syn::parse_str::<syn::Ident>(&name).is_err() {
+        return Err(syn::Error::new_spanned(
+            syn::LitStr::new(key, proc_macro2::Span::call_site()),
+            error!(format!(
+                "Metadata key {key} in {} is not a valid constant name",
+                file.display()
+            )),
+        ));
+    }
+
+    root.meta.retain(|(existing, _)| *existing != name);
+    root.meta.push((name, value));
+
+    Ok(())
+}
+
+/// Fails with an error per `required_keys` path missing from the locale
+#[cfg(feature = "json")]
+pub(super) fn check_required_keys(root: &JsonNamespace, ctx: &Context) -> syn::Result<()> {
+    let mut errors = ctx.options.required_keys.iter().filter_map(|path| {
+        let mut segments = path.split('.').peekable();
+        let mut namespace = root;
+
+        while let Some(segment) = segments.next() {
+            let last = segments.peek().is_none();
+            let found = namespace.tokens.iter().find(|token| {
+                match token {
+                    TokenJson::Namespace(namespace) => {
+                        namespace.namespace.as_deref() == Some(segment)
+                    },
+                    TokenJson::Token(token) => last && token.name == token_name(segment, ctx),
+                }
+            });
+
+            match found {
+                Some(TokenJson::Namespace(nested)) => namespace = nested,
+                Some(TokenJson::Token(_)) => return None,
+                None => {
+                    return Some(syn::Error::new_spanned(
+                        syn::LitStr::new(path, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Required key {path} is missing from {}",
+                            ctx.source
+                        )),
+                    ));
+                },
+            }
+        }
+
+        None
+    });
+
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|e| error.combine(e));
+            Err(error)
+        },
+        None => Ok(()),
+    }
+}
+
+/// Marks the keys listed in a `{"key.path": "version"}` file as deprecated
+/// when they were added after `threshold`
+#[cfg(feature = "json")]
+pub(super) fn load_versions(
+    files: &dyn FileSystem,
+    root: &mut JsonNamespace,
+    file: &path::Path,
+    threshold: &str,
+    ctx: &Context,
+) -> syn::Result<()> {
+    let versions = read_sidecar(files, file, "versions")?;
+    let parse = |version: &str| {
+        version
+            .split('.')
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                syn::Error::new_spanned(
+                    syn::LitStr::new(version, proc_macro2::Span::call_site()),
+                    error!(format!(
+                        "Invalid version {version} in {}, expected numbers separated by dots such \
+                         as 2.3.0",
+                        file.display()
+                    )),
+                )
+            })
+    };
+    let threshold = parse(threshold)?;
+
+    for (path, since) in versions {
+        let missing = || {
+            syn::Error::new_spanned(
+                syn::LitStr::new(&path, proc_macro2::Span::call_site()),
+                error!(format!(
+                    "Version of missing key {path} in {}",
+                    file.display()
+                )),
+            )
+        };
+        let mut version = parse(&since)?;
+        let tokens = root.tokens_mut(&path, ctx);
+
+        if tokens.is_empty() {
+            return Err(missing());
+        }
+
+        // Compare as equally long lists so that 2.3 and 2.3.0 are the same
+        let mut threshold = threshold.clone();
+        let len = version.len().max(threshold.len());
+        version.resize(len, 0);
+        threshold.resize(len, 0);
+
+        if version > threshold {
+            for token in tokens {
+                token.since = Some(since.clone());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the Unicode normalization form listed for each key path in a
+/// `{"key.path": "NFC"}` file
+#[cfg(all(feature = "json", feature = "normalization"))]
+pub(super) fn load_normalization(
+    files: &dyn FileSystem,
+    root: &mut JsonNamespace,
+    file: &path::Path,
+    ctx: &Context,
+) -> syn::Result<()> {
+    use unicode_normalization::UnicodeNormalization;
+
+    for (path, form) in read_sidecar(files, file, "normalization")? {
+        let normalize: fn(&str) -> String = match form.as_str() {
+            "NFC" => |s| s.nfc().collect(),
+            "NFD" => |s| s.nfd().collect(),
+            "NFKC" => |s| s.nfkc().collect(),
+            "NFKD" => |s| s.nfkd().collect(),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    syn::LitStr::new(other, proc_macro2::Span::call_site()),
+                    error!(format!(
+                        "Unknown normalization form {other} for {path} in {}, expected NFC, NFD, \
+                         NFKC or NFKD",
+                        file.display()
+                    )),
+                ));
+            },
+        };
+        let tokens = root.tokens_mut(&path, ctx);
+
+        if tokens.is_empty() {
+            return Err(syn::Error::new_spanned(
+                syn::LitStr::new(&path, proc_macro2::Span::call_site()),
+                error!(format!(
+                    "Normalization of missing key {path} in {}",
+                    file.display()
+                )),
+            ));
+        }
+
+        for token in tokens {
+            match &mut token.data {
+                TokenVariant::Single(s) => *s = normalize(s),
+                TokenVariant::Array(arr) => arr.iter_mut().for_each(|s| *s = normalize(s)),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a `{"key.path": "value"}` sidecar file, `kind` naming it in errors
+#[cfg(feature = "json")]
+fn read_sidecar(
+    files: &dyn FileSystem,
+    file: &path::Path,
+    kind: &str,
+) -> syn::Result<std::collections::BTreeMap<String, String>> {
+    let data = files.read_file(file).map_err(|e| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+            error!(format!(
+                "Cannot read {kind} file {} cause {e}",
+                file.display()
+            )),
+        )
+    })?;
+
+    serde_json::from_slice(&data).map_err(|e| deserialize_error(file, &e))
+}
+
+/// Deserializes a locale file, handling keys repeated within an object as
+/// `collisions` says
+#[cfg(feature = "json")]
+pub(super) fn from_slice(
+    data: &[u8],
+    file: &path::Path,
+    collisions: KeyCollisionStrategy,
+) -> syn::Result<serde_json::Value> {
+    use serde::de::DeserializeSeed;
+
+    let mut deserializer = serde_json::Deserializer::from_slice(data);
+
+    ValueSeed(collisions)
+        .deserialize(&mut deserializer)
+        .and_then(|value| deserializer.end().map(|()| value))
+        .map_err(|e| deserialize_error(file, &e))
+}
+
+/// `serde_json::Value` deserializer that notices repeated keys, which
+/// serde_json itself silently overwrites and serde_yaml rejects
+#[cfg(feature = "json")]
+#[derive(Clone, Copy)]
+pub(super) struct ValueSeed(pub(super) KeyCollisionStrategy);
+
+#[cfg(feature = "json")]
+impl<'de> serde::de::DeserializeSeed<'de> for ValueSeed {
+    type Value = serde_json::Value;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> serde::de::Visitor<'de> for ValueSeed {
+    type Value = serde_json::Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("any valid JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(serde_json::Value::Null)
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        // Only YAML has `.nan` and `.inf`
+        Ok(serde_json::Number::from_f64(v).map_or_else(|| v.to_string().into(), Into::into))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(v.into())
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut arr = Vec::new();
+
+        while let Some(value) = seq.next_element_seed(self)? {
+            arr.push(value);
+        }
+
+        Ok(arr.into())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut object = serde_json::Map::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+
+            match (object.contains_key(&key), self.0) {
+                (true, KeyCollisionStrategy::Error) => {
+                    return Err(serde::de::Error::custom(format!(
+                        "duplicate key `{key}`, set key_collision_strategy to \"first_wins\" or \
+                         \"last_wins\" to keep one of the values"
+                    )));
+                },
+                (true, KeyCollisionStrategy::FirstWins) => (),
+                _ => {
+                    object.insert(key, value);
+                },
+            }
+        }
+
+        Ok(object.into())
+    }
+
+    /// A YAML tag, `!Tag value`, arrives as an enum; the tag is dropped
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        use serde::de::VariantAccess;
+
+        let (_tag, value) = data.variant::<String>()?;
+
+        value.newtype_variant_seed(self)
+    }
+}
+
+/// Error for a file serde_json rejected, naming its absolute path and the
+/// position as `path:line:column` since the span can only point at the macro
+#[cfg(feature = "json")]
+pub(super) fn deserialize_error(file: &path::Path, e: &serde_json::Error) -> syn::Error {
+    let file = fs::canonicalize(file).unwrap_or_else(|_| file.to_owned());
+    let message = e.to_string();
+    let message = message
+        .rsplit_once(" at line ")
+        .map_or(message.as_str(), |(message, _)| message);
+
+    syn::Error::new_spanned(
+        syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+        error!(format!(
+            "Cannot deserialize {}:{}:{} cause {message}",
+            file.display(),
+            e.line(),
+            e.column()
+        )),
+    )
+}
+
+/// Name of the constant for `key` before case conversion
+#[cfg(feature = "json")]
+fn token_name(key: &str, ctx: &Context) -> String {
+    if ctx.options.append_file_name {
+        format!("{}__{key}", ctx.name)
+    } else {
+        key.to_owned()
+    }
+}
+
+/// Turns everything except an object into a constant
+#[cfg(feature = "json")]
+fn token(
+    name: String,
+    key: &str,
+    value: &serde_json::Value,
+    ctx: &mut Context,
+) -> syn::Result<Token> {
+    let data = match value {
+        serde_json::Value::Array(arr) => {
+            if ctx.options.strict_typed_arrays
+                && let Some(first) = arr.first()
+                && let Some(other) = arr
+                    .iter()
+                    .find(|val| std::mem::discriminant(*val) != std::mem::discriminant(first))
+            {
+                return Err(syn::Error::new_spanned(
+                    syn::LitStr::new(key, proc_macro2::Span::call_site()),
+                    error!(format!(
+                        "Array '{}' in {} mixes {first} with {other}; strict_typed_arrays \
+                         requires a single element type",
+                        ctx.key_path(key),
+                        ctx.source
+                    )),
+                ));
+            }
+
+            let mut tokens = Vec::with_capacity(arr.len());
+
+            for val in arr {
+                if matches!(val, serde_json::Value::Object(_)) {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(&val.to_string(), proc_macro2::Span::call_site()),
+                        "Everything except Object was expected",
+                    ));
+                }
+
+                if let serde_json::Value::String(s) = val {
+                    tokens.push(string(s, ctx));
+                } else {
+                    tokens.push(val.to_string())
+                }
+            }
+
+            TokenVariant::from_iter(tokens)
+        },
+        serde_json::Value::String(s) => TokenVariant::from_str(string(s, ctx)),
+        serde_json::Value::Null => {
+            match ctx.options.null_behavior {
+                NullBehavior::Empty => TokenVariant::from_str(String::new()),
+                NullBehavior::Error => {
+                    return Err(syn::Error::new_spanned(
+                        syn::LitStr::new(key, proc_macro2::Span::call_site()),
+                        error!(format!(
+                            "Key '{}' in {} has null value; set null_behavior='empty' to allow",
+                            ctx.key_path(key),
+                            ctx.source
+                        )),
+                    ));
+                },
+            }
+        },
+        serde_json::Value::Object(_) => unreachable!("objects are parsed into namespaces"),
+        val => TokenVariant::from_str(val),
+    };
+
+    validate(key, &data, ctx)?;
+    warn_long_strings(key, &data, ctx);
+    #[cfg(feature = "bidi")]
+    warn_missing_rtl_markers(key, &data, ctx);
+
+    Ok(Token {
+        name,
+        data,
+        cfg: None,
+        since: None,
+    })
+}
+
+/// Warns about strings longer than `warn_string_len`, which translators may
+/// want to shorten
+#[cfg(feature = "json")]
+fn warn_long_strings(key: &str, data: &TokenVariant, ctx: &mut Context) {
+    let Some(limit) = ctx.options.warn_string_len else {
+        return;
+    };
+    let values = match data {
+        TokenVariant::Single(s) => std::slice::from_ref(s),
+        TokenVariant::Array(arr) => arr.as_slice(),
+    };
+
+    for value in values {
+        let len = value.chars().count();
+
+        if len > limit {
+            let warning = format!(
+                "Key '{}' in {} is {len} characters long, over warn_string_len ({limit}); \
+                 consider a shorter translation",
+                ctx.key_path(key),
+                ctx.source
+            );
+
+            ctx.warnings.push(warning);
+        }
+    }
+}
+
+/// Languages written right to left
+#[cfg(feature = "bidi")]
+const RTL_LANGUAGES: [&str; 12] = [
+    "ar", "arc", "ckb", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur", "yi",
+];
+
+/// Warns about strings of a right-to-left locale that mix left-to-right and
+/// right-to-left text without a directional mark or isolate, which renderers
+/// may lay out in the wrong order
+#[cfg(feature = "bidi")]
+fn warn_missing_rtl_markers(key: &str, data: &TokenVariant, ctx: &mut Context) {
+    use unicode_bidi::{BidiClass, bidi_class};
+
+    if !ctx.options.verify_rtl_markers {
+        return;
+    }
+
+    let language = ctx
+        .name
+        .split(['_', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    if !RTL_LANGUAGES.contains(&language.as_str()) {
+        return;
+    }
+
+    let values = match data {
+        TokenVariant::Single(s) => std::slice::from_ref(s),
+        TokenVariant::Array(arr) => arr.as_slice(),
+    };
+
+    for value in values {
+        let ltr = value.chars().any(|c| bidi_class(c) == BidiClass::L);
+        let rtl = value
+            .chars()
+            .any(|c| matches!(bidi_class(c), BidiClass::R | BidiClass::AL));
+        // LRM, RLM, ALM, embeddings, overrides and isolates
+        let marked = value.chars().any(|c| {
+            matches!(
+                c,
+                '\u{200E}' | '\u{200F}' | '\u{061C}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}'
+            )
+        });
+
+        if ltr && rtl && !marked {
+            let warning = format!(
+                "Key '{}' in {} mixes left-to-right and right-to-left text without directional \
+                 marks; wrap the embedded run in RLM/LRM or an isolate",
+                ctx.key_path(key),
+                ctx.source
+            );
+
+            ctx.warnings.push(warning);
+        }
+    }
+}
+
+/// Checks every emitted string against the validation options
+#[cfg(feature = "json")]
+fn validate(key: &str, data: &TokenVariant, ctx: &Context) -> syn::Result<()> {
+    let values = match data {
+        TokenVariant::Single(s) => std::slice::from_ref(s),
+        TokenVariant::Array(arr) => arr.as_slice(),
+    };
+    let invalid = |msg: String| {
+        Err(syn::Error::new_spanned(
+            syn::LitStr::new(key, proc_macro2::Span::call_site()),
+            error!(format!(
+                "Key '{}' in {} {msg}",
+                ctx.key_path(key),
+                ctx.source
+            )),
+        ))
+    };
+
+    let is_email_field = ctx
+        .options
+        .validate_email_fields
+        .contains(&ctx.key_path(key));
+
+    for value in values {
+        let len = value.chars().count();
+
+        if ctx.options.cstring_mode
+            && matches!(data, TokenVariant::Single(_))
+            && let Some(nul) = value.find('\0')
+        {
+            return invalid(format!(
+                "has a NUL byte at {nul}, which cstring_mode cannot put in a C string"
+            ));
+        }
+
+        if is_email_field && !is_email(value) {
+            return invalid(format!("is not a valid email address: {value:?}"));
+        }
+
+        if let Some(min) = ctx.options.min_string_len
+            && len < min
+        {
+            return invalid(format!(
+                "is {len} characters long, shorter than min_string_len ({min})"
+            ));
+        }
+
+        let trailing = len - value.trim_end().chars().count();
+
+        if ctx.options.validate_no_trailing_spaces && trailing > 0 {
+            return invalid(format!("ends with {trailing} whitespace characters"));
+        }
+    }
+
+    Ok(())
+}
+
+/// `local@domain.tld` without whitespace, the domain made of non-empty labels
+#[cfg(feature = "json")]
+fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    let labels = domain.split('.').collect::<Vec<_>>();
+
+    !local.is_empty()
+        && !value.chars().any(char::is_whitespace)
+        && labels.len() > 1
+        && labels.iter().all(|label| {
+            !label.is_empty()
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        })
+}
+
+/// Applies the requested transformations to a string value
+#[cfg(feature = "json")]
+fn string(s: &str, ctx: &Context) -> String {
+    // Editors on Windows save `\r\n`, keep the constants identical everywhere
+    let s = &s.replace("\r\n", "\n");
+
+    let mut s = match ctx.options.transform {
+        Some(Transform::Uppercase) => s.to_uppercase(),
+        Some(Transform::Lowercase) => s.to_lowercase(),
+        Some(Transform::Trim) => s.trim().to_owned(),
+        Some(Transform::TitleCase) => {
+            let mut title = String::with_capacity(s.len());
+            let mut word_start = true;
+
+            for c in s.chars() {
+                if word_start {
+                    title.extend(c.to_uppercase());
+                } else {
+                    title.extend(c.to_lowercase());
+                }
+
+                word_start = c.is_whitespace();
+            }
+
+            title
+        },
+        None => s.to_owned(),
+    };
+
+    if ctx.options.sanitize_html {
+        s = strip_html_tags(&s);
+    }
+
+    if let Some(max) = ctx.options.truncate_at
+        && s.chars().count() > max
+    {
+        s = s.chars().take(max.saturating_sub(1)).chain(['…']).collect();
+    }
+
+    if ctx.options.escape_html {
+        s = escape_html(&s);
+    }
+
+    s
+}
+
+/// Drops `<tag>`, `</tag>` and `<!-- comments -->`, keeping a `<` that does
+/// not start one such as in `a < b`
+#[cfg(feature = "json")]
+fn strip_html_tags(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('<') {
+        stripped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let is_tag = rest[1..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        let end = if rest.starts_with("<!--") {
+            rest.find("-->").map(|end| end + 3)
+        } else {
+            rest.find('>').map(|end| end + 1)
+        };
+
+        match end {
+            Some(end) if is_tag => rest = &rest[end..],
+            _ => {
+                stripped.push('<');
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    stripped.push_str(rest);
+    stripped
+}
+
+#[cfg(feature = "json")]
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#x27;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
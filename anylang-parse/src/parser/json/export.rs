@@ -0,0 +1,908 @@
+//! Build artifacts written next to the generated code on request
+
+use super::*;
+
+/// Writes every artifact requested through the options
+pub(in crate::parser) fn write_all(root: &JsonNamespace, ctx: &Context) -> syn::Result<()> {
+    if let Some(path) = &ctx.options.json_schema_export {
+        let schema = serde_json::to_string_pretty(&json_schema(root, true))
+            .expect("a JSON value always serializes");
+
+        write(path, schema)?;
+    }
+
+    if let Some(path) = &ctx.options.csv_report {
+        write(path, csv_report(root))?;
+    }
+
+    if let Some(path) = &ctx.options.gettext_pot {
+        write(path, gettext_pot(root, ctx))?;
+    }
+
+    if let Some(path) = &ctx.options.pot_file {
+        write(path, pot_file(root, ctx))?;
+    }
+
+    if let Some(path) = &ctx.options.typescript_types {
+        let mut dts = String::new();
+
+        typescript_types(root, ctx.options, 0, &mut dts);
+        write(path, dts)?;
+    }
+
+    if let Some(path) = &ctx.options.graphql_schema {
+        write(path, graphql_schema(root, ctx))?;
+    }
+
+    if let Some(path) = &ctx.options.xcode_strings {
+        write(path, xcode_strings(root))?;
+    }
+
+    if let Some(path) = &ctx.options.android_xml {
+        write(path, android_xml(root))?;
+    }
+
+    if let Some(path) = &ctx.options.swift_enum {
+        let mut swift = String::from("enum Lang {\n");
+
+        swift_enum(root, 1, &mut swift);
+        swift.push_str("}\n");
+        write(path, swift)?;
+    }
+
+    if let Some(path) = &ctx.options.kotlin_object {
+        let mut kotlin = String::from("object Lang {\n");
+
+        kotlin_object(root, 1, &mut kotlin);
+        kotlin.push_str("}\n");
+        write(path, kotlin)?;
+    }
+
+    if let Some(path) = &ctx.options.c_header {
+        write(path, c_header(root, path))?;
+    }
+
+    if let Some(path) = &ctx.options.lua_table {
+        let mut lua = String::from("local Lang = {\n");
+
+        lua_table(root, 1, &mut lua);
+        lua.push_str("}\n\nreturn Lang\n");
+        write(path, lua)?;
+    }
+
+    if let Some(path) = &ctx.options.python_dict {
+        // Every value is a string or a list of them, whose JSON spelling is
+        // valid Python as well
+        let dict =
+            serde_json::to_string_pretty(&to_json(root)).expect("a JSON value always serializes");
+
+        write(path, format!("LANG = {dict}\n"))?;
+    }
+
+    if let Some(path) = &ctx.options.generate_rust_json {
+        let json =
+            serde_json::to_string_pretty(&to_json(root)).expect("a JSON value always serializes");
+
+        write(path, json)?;
+    }
+
+    #[cfg(feature = "ron")]
+    if let Some(path) = &ctx.options.ron_output {
+        let ron = ron::ser::to_string_pretty(&to_json(root), ron::ser::PrettyConfig::default())
+            .expect("a JSON value always serializes");
+
+        write(path, ron)?;
+    }
+
+    Ok(())
+}
+
+/// Compares the hash of the generated code with the one stored at `path`,
+/// storing it when there is none yet. Returns the warning to show when they
+/// differ
+pub(in crate::parser) fn check_stable_hash(
+    path: &str,
+    tokens: &proc_macro2::TokenStream,
+) -> syn::Result<Option<String>> {
+    // FNV-1a, unlike `DefaultHasher` it is the same on every Rust version
+    let hash = tokens
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    let hash = format!("{hash:016x}");
+
+    match fs::read_to_string(path) {
+        Ok(stored) if stored.trim() == hash => Ok(None),
+        Ok(_) => {
+            Ok(Some(format!(
+                "Locale changed since last stable hash; update {path} to {hash}"
+            )))
+        },
+        Err(e) if e.kind() == io::ErrorKind::NotFound => write(path, hash).map(|()| None),
+        Err(e) => {
+            Err(syn::Error::new_spanned(
+                syn::LitStr::new(path, proc_macro2::Span::call_site()),
+                error!(format!("Cannot read {path} cause {e}")),
+            ))
+        },
+    }
+}
+
+/// Adds the absolute paths of `inputs` to `$OUT_DIR/anylang_inputs.txt`, one
+/// per line. Every expansion in the crate shares the file, so the lines of
+/// earlier ones are kept
+pub(in crate::parser) fn write_inputs(inputs: &[path::PathBuf]) -> syn::Result<()> {
+    let path = out_dir("watch")?.join("anylang_inputs.txt");
+    let mut lines = fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .map(str::to_owned)
+        .collect::<std::collections::BTreeSet<_>>();
+
+    lines.extend(inputs.iter().map(|input| {
+        fs::canonicalize(input)
+            .unwrap_or_else(|_| input.clone())
+            .display()
+            .to_string()
+    }));
+
+    let contents = lines.into_iter().map(|line| line + "\n").collect();
+
+    write(&path.display().to_string(), contents)
+}
+
+/// Writes a top-level module to `$OUT_DIR/<file>` for `segment_by_namespace`
+/// and returns the `include!` taking its place
+pub(in crate::parser) fn write_segment(
+    file: &str,
+    item: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let written = out_dir("segment_by_namespace").and_then(|out_dir| {
+        let path = out_dir.join(file).display().to_string();
+
+        write(&path, item.to_string()).map(|()| path)
+    });
+
+    match written {
+        Ok(path) => quote! { include!(#path); },
+        Err(e) => e.to_compile_error(),
+    }
+}
+
+/// Writes a Markdown table with a row per key path of the first of `locales`,
+/// its type and a column per locale with its value there
+pub(in crate::parser) fn write_markdown<'a>(
+    path: &str,
+    locales: impl IntoIterator<Item = &'a File>,
+) -> syn::Result<()> {
+    let cell = |data: Option<&TokenVariant>| {
+        let value = match data {
+            Some(TokenVariant::Single(value)) => value.clone(),
+            Some(TokenVariant::Array(arr)) => {
+                format!(
+                    "[{}]",
+                    arr.iter()
+                        .map(|value| format!("{value:?}"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+            None => String::new(),
+        };
+
+        value
+            .replace('|', "\\|")
+            .replace("\r\n", "<br>")
+            .replace(['\r', '\n'], "<br>")
+    };
+    let locales = locales
+        .into_iter()
+        .map(|locale| {
+            let mut values = HashMap::new();
+
+            locale
+                .tokens
+                .for_each_token(&mut Vec::new(), &mut |path, token| {
+                    let key = path
+                        .iter()
+                        .copied()
+                        .chain([token.name.as_str()])
+                        .collect::<Vec<_>>()
+                        .join(".");
+
+                    values.insert(key, &token.data);
+                });
+
+            (locale.name.as_str(), values)
+        })
+        .collect::<Vec<_>>();
+    let Some((_, reference)) = locales.first() else {
+        return Ok(());
+    };
+    let mut keys = reference.iter().collect::<Vec<_>>();
+
+    keys.sort_by_key(|(key, _)| *key);
+
+    let names = locales.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    let mut md = format!(
+        "| Key Path | Type | {} |\n|---|---|{}\n",
+        names.join(" | "),
+        "---|".repeat(names.len())
+    );
+
+    for (key, data) in keys {
+        let ty = match data {
+            TokenVariant::Single(_) => "string",
+            TokenVariant::Array(_) => "array",
+        };
+        let values = locales
+            .iter()
+            .map(|(_, values)| cell(values.get(key).copied()))
+            .collect::<Vec<_>>();
+
+        md.push_str(&format!("| `{key}` | {ty} | {} |\n", values.join(" | ")));
+    }
+
+    write(path, md)
+}
+
+/// `$OUT_DIR` of the crate being compiled, required by `option`
+fn out_dir(option: &str) -> syn::Result<path::PathBuf> {
+    std::env::var_os("OUT_DIR")
+        .map(path::PathBuf::from)
+        .ok_or_else(|| {
+            syn::Error::new_spanned(
+                syn::LitStr::new(option, proc_macro2::Span::call_site()),
+                error!(format!(
+                    "{option} needs OUT_DIR, which Cargo only sets for crates with a build script"
+                )),
+            )
+        })
+}
+
+/// Writes `contents` to `path`, creating missing parent directories
+fn write(path: &str, contents: String) -> syn::Result<()> {
+    let path = path::Path::new(path);
+
+    path.parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents))
+        .map_err(|e| {
+            syn::Error::new_spanned(
+                syn::LitStr::new(&path.display().to_string(), proc_macro2::Span::call_site()),
+                error!(format!("Cannot write {} cause {e}", path.display())),
+            )
+        })
+}
+
+/// The parsed tree as JSON again, with every value a string or an array of
+/// strings as in the generated constants
+fn to_json(namespace: &JsonNamespace) -> serde_json::Value {
+    let object = namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+        .map(|token| {
+            let value = match token {
+                TokenJson::Namespace(namespace) => to_json(namespace),
+                TokenJson::Token(token) => {
+                    match &token.data {
+                        TokenVariant::Single(s) => serde_json::json!(s),
+                        TokenVariant::Array(arr) => serde_json::json!(arr),
+                    }
+                },
+            };
+
+            (token.name().to_owned(), value)
+        })
+        .collect();
+
+    serde_json::Value::Object(object)
+}
+
+/// JSON Schema requiring every key of `namespace`, recursively
+fn json_schema(namespace: &JsonNamespace, root: bool) -> serde_json::Value {
+    let scalar = serde_json::json!(["string", "number", "boolean", "null"]);
+    let mut properties = serde_json::Map::new();
+
+    for token in namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+    {
+        let schema = match token {
+            TokenJson::Namespace(namespace) => json_schema(namespace, false),
+            TokenJson::Token(Token {
+                data: TokenVariant::Single(_),
+                ..
+            }) => serde_json::json!({ "type": scalar }),
+            TokenJson::Token(Token {
+                data: TokenVariant::Array(arr),
+                ..
+            }) => {
+                serde_json::json!({
+                    "type": "array",
+                    "items": { "type": scalar },
+                    "minItems": arr.len(),
+                    "maxItems": arr.len(),
+                })
+            },
+        };
+
+        properties.insert(token.name().to_owned(), schema);
+    }
+
+    let mut schema = serde_json::json!({
+        "type": "object",
+        "required": properties.keys().collect::<Vec<_>>(),
+        "properties": properties,
+        "additionalProperties": false,
+    });
+
+    if root {
+        schema["$schema"] = "https://json-schema.org/draft/2020-12/schema".into();
+    }
+
+    schema
+}
+
+/// Dotted key path and value of every string, arrays expanded to `key.N`
+fn entries(root: &JsonNamespace) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let key = path
+            .iter()
+            .copied()
+            .chain([token.name.as_str()])
+            .collect::<Vec<_>>()
+            .join(".");
+
+        match &token.data {
+            TokenVariant::Single(value) => entries.push((key, value.clone())),
+            TokenVariant::Array(arr) => {
+                for (i, value) in arr.iter().enumerate() {
+                    entries.push((format!("{key}.{i}"), value.clone()));
+                }
+            },
+        }
+    });
+
+    entries
+}
+
+/// `s` in double quotes with C-style escapes, as `.pot` and `.strings` files
+/// expect
+fn quoted(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n");
+
+    format!("\"{escaped}\"")
+}
+
+/// Apple `.strings` file with a `"key" = "value";` line per string
+fn xcode_strings(root: &JsonNamespace) -> String {
+    entries(root)
+        .into_iter()
+        .map(|(key, value)| format!("{} = {};\n", quoted(&key), quoted(&value)))
+        .collect()
+}
+
+/// C header with a `LANG_<PATH>` `#define` per string and a brace list per
+/// array, behind an include guard named after `path`
+fn c_header(root: &JsonNamespace, path: &str) -> String {
+    let guard = path::Path::new(path)
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .chars()
+        .map(|c| {
+            match c.is_ascii_alphanumeric() {
+                true => c.to_ascii_uppercase(),
+                false => '_',
+            }
+        })
+        .collect::<String>();
+    let literal = |s: &str| {
+        // Hex escapes would swallow the digits that follow them, so other
+        // control characters become octal ones. The rest stays UTF-8
+        let escaped = s
+            .chars()
+            .map(|c| {
+                match c {
+                    '\\' => "\\\\".to_owned(),
+                    '"' => "\\\"".to_owned(),
+                    '\n' => "\\n".to_owned(),
+                    '\t' => "\\t".to_owned(),
+                    '\r' => "\\r".to_owned(),
+                    c if c.is_ascii_control() => format!("\\{:03o}", u32::from(c)),
+                    c => c.to_string(),
+                }
+            })
+            .collect::<String>();
+
+        format!("\"{escaped}\"")
+    };
+    let mut header = format!("#ifndef {guard}\n#define {guard}\n\n");
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let name = ["lang"]
+            .into_iter()
+            .chain(path.iter().copied())
+            .chain([token.name.as_str()])
+            .flat_map(split_words)
+            .map(|word| word.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_");
+        let value = match &token.data {
+            TokenVariant::Single(value) => literal(value),
+            TokenVariant::Array(arr) => {
+                format!(
+                    "{{ {} }}",
+                    arr.iter()
+                        .map(|value| literal(value))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            },
+        };
+
+        header.push_str(&format!("#define {name} {value}\n"));
+    });
+
+    header.push_str(&format!("\n#endif /* {guard} */\n"));
+    header
+}
+
+/// Fields of a Lua table per namespace, keyed by the keys as written so that
+/// keys differing in case stay apart
+fn lua_table(namespace: &JsonNamespace, depth: usize, lua: &mut String) {
+    const KEYWORDS: &[&str] = &[
+        "and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if",
+        "in", "local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+    ];
+    let indent = "    ".repeat(depth);
+    let literal = |s: &str| {
+        let escaped = s
+            .chars()
+            .map(|c| {
+                match c {
+                    '\\' => "\\\\".to_owned(),
+                    '"' => "\\\"".to_owned(),
+                    '\n' => "\\n".to_owned(),
+                    '\t' => "\\t".to_owned(),
+                    '\r' => "\\r".to_owned(),
+                    c if c.is_ascii_control() => format!("\\{:03}", u32::from(c)),
+                    c => c.to_string(),
+                }
+            })
+            .collect::<String>();
+
+        format!("\"{escaped}\"")
+    };
+    // Keywords and keys that are no Lua names need the `["key"]` form
+    let field = |name: &str| {
+        let is_name = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && !KEYWORDS.contains(&name);
+
+        match is_name {
+            true => name.to_owned(),
+            false => format!("[{}]", literal(name)),
+        }
+    };
+
+    for token in namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+    {
+        match token {
+            TokenJson::Namespace(namespace) => {
+                lua.push_str(&format!("{indent}{} = {{\n", field(token.name())));
+                lua_table(namespace, depth + 1, lua);
+                lua.push_str(&format!("{indent}}},\n"));
+            },
+            TokenJson::Token(token) => {
+                let value = match &token.data {
+                    TokenVariant::Single(value) => literal(value),
+                    TokenVariant::Array(arr) => {
+                        format!(
+                            "{{ {} }}",
+                            arr.iter()
+                                .map(|value| literal(value))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    },
+                };
+
+                lua.push_str(&format!("{indent}{} = {value},\n", field(&token.name)));
+            },
+        }
+    }
+}
+
+/// Android `res/values/strings.xml`, the key path joined with `_` as the
+/// resource name and arrays as `<string-array>`
+fn android_xml(root: &JsonNamespace) -> String {
+    // XML entities first, then the backslash escapes `aapt` expects
+    let escape = |s: &str| {
+        let escaped = s
+            .replace('\\', "\\\\")
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('\'', "\\'")
+            .replace('"', "\\\"")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n");
+
+        match escaped.starts_with(['@', '?']) {
+            true => format!("\\{escaped}"),
+            false => escaped,
+        }
+    };
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n");
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let name = path
+            .iter()
+            .copied()
+            .chain([token.name.as_str()])
+            .collect::<Vec<_>>()
+            .join("_");
+
+        match &token.data {
+            TokenVariant::Single(value) => {
+                xml.push_str(&format!(
+                    "    <string name=\"{name}\">{}</string>\n",
+                    escape(value)
+                ));
+            },
+            TokenVariant::Array(arr) => {
+                xml.push_str(&format!("    <string-array name=\"{name}\">\n"));
+
+                for value in arr {
+                    xml.push_str(&format!("        <item>{}</item>\n", escape(value)));
+                }
+
+                xml.push_str("    </string-array>\n");
+            },
+        }
+    });
+
+    xml.push_str("</resources>\n");
+    xml
+}
+
+/// Swift `enum` per namespace with a `static let` per constant
+fn swift_enum(namespace: &JsonNamespace, depth: usize, swift: &mut String) {
+    const KEYWORDS: &[&str] = &[
+        "as",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "continue",
+        "default",
+        "defer",
+        "do",
+        "else",
+        "enum",
+        "extension",
+        "false",
+        "for",
+        "func",
+        "guard",
+        "if",
+        "import",
+        "in",
+        "init",
+        "is",
+        "let",
+        "nil",
+        "protocol",
+        "repeat",
+        "return",
+        "self",
+        "static",
+        "struct",
+        "super",
+        "switch",
+        "throw",
+        "throws",
+        "true",
+        "try",
+        "var",
+        "where",
+        "while",
+    ];
+    let indent = "    ".repeat(depth);
+    let identifier = |name: String| {
+        match KEYWORDS.contains(&name.as_str()) {
+            true => format!("`{name}`"),
+            false if name.starts_with(|c: char| c.is_ascii_digit()) => format!("_{name}"),
+            false => name,
+        }
+    };
+    let literal = |s: &str| {
+        let escaped = s
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\0', "\\0")
+            .replace('\t', "\\t")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n");
+
+        format!("\"{escaped}\"")
+    };
+
+    for token in namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+    {
+        match token {
+            TokenJson::Namespace(namespace) => {
+                let name = identifier(upper_camel([token.name()]));
+
+                swift.push_str(&format!("{indent}enum {name} {{\n"));
+                swift_enum(namespace, depth + 1, swift);
+                swift.push_str(&format!("{indent}}}\n"));
+            },
+            TokenJson::Token(token) => {
+                let name = identifier(lower_camel([token.name.as_str()]));
+                let value = match &token.data {
+                    TokenVariant::Single(value) => literal(value),
+                    TokenVariant::Array(arr) => {
+                        format!(
+                            "[{}]",
+                            arr.iter()
+                                .map(|value| literal(value))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    },
+                };
+                let ty = match token.data {
+                    TokenVariant::Single(_) => "String",
+                    TokenVariant::Array(_) => "[String]",
+                };
+
+                swift.push_str(&format!("{indent}static let {name}: {ty} = {value}\n"));
+            },
+        }
+    }
+}
+
+/// Kotlin `object` per namespace with a `SCREAMING_SNAKE_CASE` property per
+/// constant, `const val` for strings and `val` lists for arrays
+fn kotlin_object(namespace: &JsonNamespace, depth: usize, kotlin: &mut String) {
+    let indent = "    ".repeat(depth);
+    let identifier = |name: String| {
+        match name.starts_with(|c: char| c.is_ascii_digit()) {
+            true => format!("_{name}"),
+            false => name,
+        }
+    };
+    let literal = |s: &str| {
+        let escaped = s
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('$', "\\$")
+            .replace('\t', "\\t")
+            .replace('\r', "\\r")
+            .replace('\n', "\\n");
+
+        format!("\"{escaped}\"")
+    };
+
+    for token in namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+    {
+        match token {
+            TokenJson::Namespace(namespace) => {
+                let name = identifier(upper_camel([token.name()]));
+
+                kotlin.push_str(&format!("{indent}object {name} {{\n"));
+                kotlin_object(namespace, depth + 1, kotlin);
+                kotlin.push_str(&format!("{indent}}}\n"));
+            },
+            TokenJson::Token(token) => {
+                let name = identifier(
+                    split_words(&token.name)
+                        .iter()
+                        .map(|word| word.to_uppercase())
+                        .collect::<Vec<_>>()
+                        .join("_"),
+                );
+                let property = match &token.data {
+                    TokenVariant::Single(value) => {
+                        format!("const val {name} = {}", literal(value))
+                    },
+                    TokenVariant::Array(arr) => {
+                        format!(
+                            "val {name}: List<String> = listOf({})",
+                            arr.iter()
+                                .map(|value| literal(value))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                    },
+                };
+
+                kotlin.push_str(&format!("{indent}{property}\n"));
+            },
+        }
+    }
+}
+
+/// `UpperCamelCase` of the words of `parts`
+fn upper_camel<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    parts
+        .into_iter()
+        .flat_map(split_words)
+        .map(|word| capitalize(&word))
+        .collect()
+}
+
+/// `lowerCamelCase` of the words of `parts`
+fn lower_camel<'a>(parts: impl IntoIterator<Item = &'a str>) -> String {
+    parts
+        .into_iter()
+        .flat_map(split_words)
+        .enumerate()
+        .map(|(i, word)| {
+            match i {
+                0 => word.to_lowercase(),
+                _ => capitalize(&word),
+            }
+        })
+        .collect()
+}
+
+/// RFC 4180 `key,value` rows
+fn csv_report(root: &JsonNamespace) -> String {
+    let mut csv = String::from("key,value\r\n");
+
+    for (key, value) in entries(root) {
+        for field in [&key, &value] {
+            if field.contains([',', '"', '\r', '\n']) {
+                csv.push('"');
+                csv.push_str(&field.replace('"', "\"\""));
+                csv.push('"');
+            } else {
+                csv.push_str(field);
+            }
+
+            csv.push(',');
+        }
+
+        csv.pop();
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+/// Header entry every `.pot` file starts with
+const POT_HEADER: &str = r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Content-Transfer-Encoding: 8bit\n"
+"#;
+
+/// gettext template keyed by the current values, as in the usual gettext
+/// workflow. Repeated values share one entry listing every key path
+fn pot_file(root: &JsonNamespace, ctx: &Context) -> String {
+    let mut msgids: Vec<(String, Vec<String>)> = Vec::new();
+
+    // An empty `msgid` is reserved for the header
+    for (key, value) in entries(root)
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+    {
+        match msgids.iter_mut().find(|(msgid, _)| *msgid == value) {
+            Some((_, keys)) => keys.push(key),
+            None => msgids.push((value, vec![key])),
+        }
+    }
+
+    let mut pot = String::from(POT_HEADER);
+
+    for (msgid, keys) in msgids {
+        pot.push_str(&format!("\n#. {}\n", keys.join(", ")));
+        pot.push_str(&format!("#: {}\n", ctx.source));
+        pot.push_str(&format!("msgid {}\nmsgstr \"\"\n", quoted(&msgid)));
+    }
+
+    pot
+}
+
+/// gettext template keyed by path, the current value kept as a translator
+/// comment
+fn gettext_pot(root: &JsonNamespace, ctx: &Context) -> String {
+    let mut pot = String::from(POT_HEADER);
+
+    for (key, value) in entries(root) {
+        pot.push('\n');
+
+        for line in value.lines() {
+            pot.push_str(&format!("#. {line}\n"));
+        }
+
+        pot.push_str(&format!("#: {}\n", ctx.source));
+        pot.push_str(&format!("msgid {}\nmsgstr \"\"\n", quoted(&key)));
+    }
+
+    pot
+}
+
+/// `.d.ts` declarations mirroring the generated modules and constants
+fn typescript_types(namespace: &JsonNamespace, options: &Options, depth: usize, dts: &mut String) {
+    let indent = "    ".repeat(depth);
+
+    for token in namespace
+        .tokens
+        .iter()
+        .filter(|token| token.is_unconditional())
+    {
+        match token {
+            TokenJson::Namespace(namespace) => {
+                dts.push_str(&format!(
+                    "{indent}export namespace {} {{\n",
+                    module_name(token.name(), options)
+                ));
+                typescript_types(namespace, options, depth + 1, dts);
+                dts.push_str(&format!("{indent}}}\n"));
+            },
+            TokenJson::Token(token) => {
+                let ty = match &token.data {
+                    TokenVariant::Single(_) => "string".to_owned(),
+                    TokenVariant::Array(arr) => {
+                        format!("readonly [{}]", vec!["string"; arr.len()].join(", "))
+                    },
+                };
+
+                dts.push_str(&format!(
+                    "{indent}export const {}: {ty};\n",
+                    const_name(&token.name, options)
+                ));
+            },
+        }
+    }
+}
+
+/// GraphQL object type with a `lowerCamelCase` field per constant:
+/// `type LangEnUs { ping: String! dummySome: [String!]! }`
+fn graphql_schema(root: &JsonNamespace, ctx: &Context) -> String {
+    let stem = path::Path::new(&ctx.source)
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .replace('.', "_");
+    let type_name = upper_camel([stem.as_str()]);
+    let mut schema = format!("type Lang{type_name} {{\n");
+
+    root.for_each_token(&mut Vec::new(), &mut |path, token| {
+        let field = lower_camel(path.iter().copied().chain([token.name.as_str()]));
+        let ty = match token.data {
+            TokenVariant::Single(_) => "String!",
+            TokenVariant::Array(_) => "[String!]!",
+        };
+
+        schema.push_str(&format!("  {field}: {ty}\n"));
+    });
+
+    schema.push_str("}\n");
+    schema
+}
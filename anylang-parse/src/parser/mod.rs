@@ -0,0 +1,889 @@
+macro_rules! error {
+    ($err:expr) => {
+        format!("[{}:parse:ERROR] {}", crate::CRATE_NAME, $err)
+    };
+}
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "toml")]
+mod toml;
+#[cfg(feature = "yaml")]
+mod yaml;
+
+#[cfg(feature = "base64")]
+use crate::options::Encoding;
+use crate::{
+    file_system::{FileSystem, RealFileSystem},
+    options::{KeyCollisionStrategy, NameTransform, NullBehavior, Options, Transform},
+};
+use std::{ffi, fs, io, path};
+
+#[cfg(feature = "json")]
+use json::*;
+#[cfg(feature = "json")]
+pub use json::{Context, JsonNamespace, LocaleDiff, parse_json};
+
+#[derive(PartialEq)]
+pub enum TokenVariant {
+    Single(String),
+    Array(Vec<String>),
+}
+
+impl TokenVariant {
+    fn into_data(self, options: &Options) -> proc_macro2::TokenStream {
+        match self {
+            // `validate` rejected strings with a NUL byte
+            Self::Single(s) if options.cstring_mode => {
+                let cstring = std::ffi::CString::new(s).expect("no NUL byte in C string");
+                let lit = proc_macro2::Literal::c_string(&cstring);
+                quote::quote!(#lit)
+            },
+            Self::Single(s) => {
+                let lit = syn::LitStr::new(&s, proc_macro2::Span::call_site());
+                quote::quote!(#lit)
+            },
+            Self::Array(arr) => {
+                let items = arr
+                    .iter()
+                    .map(|s| syn::LitStr::new(s, proc_macro2::Span::call_site()));
+                quote::quote!([#(#items),*])
+            },
+        }
+    }
+
+    /// Spelled with `'static` so the type also fits outside of a `const`
+    fn get_type(&self, options: &Options) -> syn::Type {
+        // Constants are `'static` anyway, but the `const_len_array` newtype
+        // field needs the lifetime spelled out
+        let lifetime = match (self, options.suppress_type_annotations) {
+            (Self::Array(_), true) if options.const_len_array => "'static ",
+            (_, true) => "",
+            (_, false) => "'static ",
+        };
+
+        match self {
+            Self::Single(_) if options.cstring_mode => {
+                syn::parse_str(&format!("&{lifetime}::core::ffi::CStr")).unwrap()
+            },
+            Self::Single(_) => syn::parse_str(&format!("&{lifetime}str")).unwrap(),
+            Self::Array(arr) => {
+                syn::parse_str(&format!("[&{lifetime}str; {}]", arr.len())).unwrap()
+            },
+        }
+    }
+}
+
+impl TokenVariant {
+    fn from_str<T>(value: T) -> Self
+    where
+        T: ToString,
+    {
+        Self::Single(value.to_string())
+    }
+
+    fn from_iter<I>(value: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self::Array(value.into_iter().collect())
+    }
+}
+
+/// Name of the `const_len_array` newtype wrapping the array of `key`:
+/// `SomeArray`
+#[cfg(feature = "json")]
+fn array_type_name(key: &str) -> String {
+    let words = split_words(key);
+
+    format!(
+        "{}Array",
+        words
+            .iter()
+            .map(|word| capitalize(word))
+            .collect::<String>()
+    )
+}
+
+/// Name of the constant generated for `key`
+fn const_name(key: &str, options: &Options) -> String {
+    let Some(transform) = options.name_transform else {
+        return key.to_uppercase();
+    };
+
+    let words = split_words(key);
+
+    match transform {
+        NameTransform::ScreamingSnake => words.join("_").to_uppercase(),
+        NameTransform::ScreamingKebab => words.join("__").to_uppercase(),
+        NameTransform::LowerSnake => words.join("_").to_lowercase(),
+        NameTransform::Kebab => words.join("__").to_lowercase(),
+        NameTransform::UpperCamel => words.iter().map(|word| capitalize(word)).collect(),
+        NameTransform::LowerCamel => {
+            words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect()
+        },
+    }
+}
+
+/// Splits a key into words on `_`, `-`, spaces and camelCase humps
+fn split_words(key: &str) -> Vec<String> {
+    let mut words = Vec::<String>::new();
+    let mut prev_lower = false;
+
+    for c in key.chars() {
+        if matches!(c, '_' | '-' | ' ') {
+            prev_lower = false;
+            words.push(String::new());
+            continue;
+        }
+
+        if words.is_empty() || (prev_lower && c.is_uppercase()) {
+            words.push(String::new());
+        }
+
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        words.last_mut().unwrap().push(c);
+    }
+
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Uppercases the first letter of `word` and lowercases the rest
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars.flat_map(char::to_lowercase))
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[derive(PartialEq)]
+struct Token {
+    name: String,
+    data: TokenVariant,
+    cfg: Option<Cfg>,
+    /// Version the key was added in, when newer than `deprecate_since`
+    since: Option<String>,
+}
+
+/// Condition attached to an item by a `_cfg_` block
+#[derive(Clone, PartialEq)]
+enum Cfg {
+    /// Only compiled when the predicate holds: `target_os = "ios"`
+    Only(String),
+    /// Fallback compiled when none of the predicates hold
+    Not(Vec<String>),
+}
+
+impl Cfg {
+    fn to_attr(&self) -> proc_macro2::TokenStream {
+        let predicate = match self {
+            Self::Only(predicate) => predicate.clone(),
+            Self::Not(predicates) => format!("not(any({}))", predicates.join(", ")),
+        };
+        let predicate = predicate
+            .parse::<proc_macro2::TokenStream>()
+            .expect("cfg predicates are built from identifiers and string literals");
+
+        quote::quote! { #[cfg(#predicate)] }
+    }
+}
+
+/// `cfg` names accepted as `_cfg_<name>_<value>`
+const CFG_NAMES: [&str; 8] = [
+    "target_pointer_width",
+    "target_endian",
+    "target_family",
+    "target_vendor",
+    "target_arch",
+    "target_env",
+    "target_os",
+    "feature",
+];
+
+/// `cfg` flags accepted as `_cfg_<flag>`
+const CFG_FLAGS: [&str; 4] = ["unix", "windows", "test", "debug_assertions"];
+
+/// Turns the part of a key after `_cfg_` into a `cfg` predicate:
+/// `target_os_ios` becomes `target_os = "ios"`
+fn cfg_predicate(condition: &str) -> Option<String> {
+    if CFG_FLAGS.contains(&condition) {
+        return Some(condition.to_owned());
+    }
+
+    CFG_NAMES.iter().find_map(|name| {
+        condition
+            .strip_prefix(name)
+            .and_then(|value| value.strip_prefix('_'))
+            .filter(|value| !value.is_empty())
+            .map(|value| format!("{name} = {value:?}"))
+    })
+}
+
+/// Compile warning on stable Rust: using a deprecated item reports its note
+fn warning(message: &str) -> proc_macro2::TokenStream {
+    quote::quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            const ANYLANG_WARNING: () = ();
+
+            ANYLANG_WARNING
+        };
+    }
+}
+
+pub struct File {
+    pub name: String,
+    #[cfg(feature = "json")]
+    tokens: JsonNamespace,
+    options: Options,
+    /// Shown as compile warnings next to the generated code
+    warnings: Vec<String>,
+    /// Absolute path of a Rust source file to `include!` instead of generated
+    /// code
+    include: Option<String>,
+}
+
+/// Parses the locale at `file` on disk
+pub fn parse_from_file(file: path::PathBuf, options: &Options) -> syn::Result<File> {
+    parse_from_fs(&RealFileSystem, file, options)
+}
+
+/// Parses the locale at `file`, reading it and everything next to it from
+/// `files`
+pub fn parse_from_fs(
+    files: &dyn FileSystem,
+    file: path::PathBuf,
+    options: &Options,
+) -> syn::Result<File> {
+    let file_name = if let Some(file_name) = file.file_stem() {
+        file_name.to_string_lossy().to_string()
+    } else {
+        return Err(syn::Error::new_spanned(
+            syn::LitStr::new(
+                file.to_string_lossy().as_ref(),
+                proc_macro2::Span::call_site(),
+            ),
+            error!(format!(
+                "File expected but received {}",
+                file.to_string_lossy().to_string()
+            )),
+        ));
+    };
+    let extension = match file.extension() {
+        Some(extension)
+            if !options.auto_detect
+                || ["json", "rs", "toml", "yaml", "yml"]
+                    .map(ffi::OsStr::new)
+                    .contains(&extension) =>
+        {
+            Some(extension.to_owned())
+        },
+        _ if options.auto_detect => Some(detect_format(files, &file)?.into()),
+        _ => None,
+    };
+
+    if let Some(extension) = extension {
+        // A module written in Rust by hand is compiled as is
+        #[cfg(feature = "json")]
+        if extension == "rs" {
+            let include = files.canonicalize(&file).map_err(|e| {
+                syn::Error::new_spanned(
+                    syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+                    error!(format!("Cannot read file {file_name} cause {e}")),
+                )
+            })?;
+
+            return Ok(File {
+                name: file_name,
+                tokens: JsonNamespace::default(),
+                options: options.clone(),
+                warnings: Vec::new(),
+                include: Some(include.display().to_string()),
+            });
+        }
+
+        #[cfg(feature = "json")]
+        if locale_format(&extension).is_some() {
+            let source = file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            // Every file the expansion depends on, for `watch`
+            let mut inputs = Vec::new();
+            let (mut value, frontmatter) =
+                read_locale(files, &file, &extension, options, &mut inputs)?;
+            let metadata = take_metadata(&mut value, options, &file)?;
+
+            let mut ctx = Context::new(&file_name, source, options);
+            let mut root_namespace = JsonNamespace::default();
+            parse_json(&value, &mut root_namespace, &mut ctx)?;
+
+            for fallback in [&options.fallback, &options.final_fallback]
+                .into_iter()
+                .flatten()
+            {
+                let fallback_source = format!("{fallback}.{}", extension.to_string_lossy());
+                let fallback_file = file.with_file_name(&fallback_source);
+                let (mut value, _) =
+                    read_locale(files, &fallback_file, &extension, options, &mut inputs)?;
+
+                take_metadata(&mut value, options, &fallback_file)?;
+                // Named after the requested locale so the constants line up
+                let mut fallback_ctx = Context::new(&file_name, fallback_source, options);
+                let mut fallback_namespace = JsonNamespace::default();
+                parse_json(&value, &mut fallback_namespace, &mut fallback_ctx)?;
+
+                ctx.warnings.append(&mut fallback_ctx.warnings);
+                root_namespace.merge_missing(fallback_namespace);
+            }
+
+            if let Some(compat) = &options.compat_aliases {
+                let compat = file.parent().unwrap_or(path::Path::new("")).join(compat);
+
+                load_compat_aliases(files, &mut root_namespace, &compat, &ctx)?;
+                inputs.push(compat);
+            }
+
+            if let Some(threshold) = &options.deprecate_since {
+                let versions = file.with_file_name(format!("{file_name}.versions.json"));
+
+                load_versions(files, &mut root_namespace, &versions, threshold, &ctx)?;
+                inputs.push(versions);
+            }
+
+            #[cfg(feature = "normalization")]
+            if options.normalization_file {
+                let normalization = file.with_file_name(format!("{file_name}.normalization.json"));
+
+                load_normalization(files, &mut root_namespace, &normalization, &ctx)?;
+                inputs.push(normalization);
+            }
+
+            for (key, value) in frontmatter.into_iter().chain(metadata) {
+                add_meta(&mut root_namespace, &key, value, &file)?;
+            }
+
+            let info = file.with_file_name(format!("{file_name}.info.json"));
+
+            if files.is_file(&info) {
+                load_info(files, &mut root_namespace, &info)?;
+                inputs.push(info);
+            }
+
+            check_required_keys(&root_namespace, &ctx)?;
+            // Every export lists the keys in the order of the generated code
+            root_namespace.sort();
+            write_all(&root_namespace, &ctx)?;
+
+            if options.watch {
+                write_inputs(&inputs)?;
+            }
+
+            return Ok(File {
+                name: file_name,
+                tokens: root_namespace,
+                options: options.clone(),
+                warnings: ctx.warnings,
+                include: None,
+            });
+        } else {
+            // Picked by `locale_priority` or as the only file of the language
+            return Err(syn::Error::new_spanned(
+                syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+                error!(format!(
+                    "Cannot read {} as a locale cause no reader handles .{} files, expected \
+                     .json, .rs or, with their features, .toml and .yaml",
+                    file.display(),
+                    extension.to_string_lossy()
+                )),
+            ));
+        }
+
+        const ERR_MSG: &str = "No one feature was choosed!";
+
+        #[allow(unused)]
+        Err(syn::Error::new_spanned(
+            syn::LitStr::new(ERR_MSG, proc_macro2::Span::call_site()),
+            error!(ERR_MSG),
+        ))
+    } else {
+        Err(syn::Error::new_spanned(
+            syn::LitStr::new(&file_name, proc_macro2::Span::call_site()),
+            error!("A file with some extension was expected"),
+        ))
+    }
+}
+
+/// Extension matching the content of a locale whose extension is wrong or
+/// missing, told by its first bytes other than whitespace and a BOM
+fn detect_format(files: &dyn FileSystem, file: &path::Path) -> syn::Result<&'static str> {
+    let bytes = files.read_file(file).map_err(|e| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+            error!(format!("Cannot read file {} cause {e}", file.display())),
+        )
+    })?;
+    let content = bytes
+        .strip_prefix(b"\xEF\xBB\xBF")
+        .unwrap_or(&bytes)
+        .trim_ascii_start();
+    let error = |message: &str| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+            error!(format!(
+                "Cannot detect the format of {} cause {message}",
+                file.display()
+            )),
+        )
+    };
+
+    match content {
+        [b'{' | b'[', ..] => Ok("json"),
+        #[cfg(feature = "yaml")]
+        [b'-', b'-', b'-', ..] => Ok("yaml"),
+        #[cfg(not(feature = "yaml"))]
+        [b'-', b'-', b'-', ..] => Err(error("it looks like YAML, which needs the `yaml` feature")),
+        _ => Err(error("it starts with neither `{`, `[` nor `---`")),
+    }
+}
+
+/// Reads a JSON, TOML or YAML locale, told by `extension`, as the JSON value
+/// the rest of the parsing works on, with its `$include`s, decoded as the
+/// options require, and the `key: value` pairs of its frontmatter. Adds the
+/// files read to `inputs`
+#[cfg(feature = "json")]
+fn read_locale(
+    files: &dyn FileSystem,
+    file: &path::Path,
+    extension: &ffi::OsStr,
+    options: &Options,
+    inputs: &mut Vec<path::PathBuf>,
+) -> syn::Result<(serde_json::Value, Vec<(String, String)>)> {
+    let file_name = file.file_stem().unwrap_or_default().to_string_lossy();
+    let mut bytes = files.read_file(file).map_err(|e| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+            error!(format!("Cannot read file {file_name} cause {e}")),
+        )
+    })?;
+
+    inputs.push(file.to_owned());
+
+    #[cfg(feature = "checksum")]
+    if options.checksum_file {
+        verify_checksum(files, file, &bytes)?;
+
+        let mut checksum_path = file.as_os_str().to_owned();
+        checksum_path.push(".sha256");
+        inputs.push(checksum_path.into());
+    }
+
+    #[cfg(feature = "base64")]
+    if options.encoding == Encoding::Base64 {
+        use base64::Engine;
+
+        bytes = base64::engine::general_purpose::STANDARD
+            .decode(bytes.trim_ascii())
+            .map_err(|e| {
+                syn::Error::new_spanned(
+                    syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+                    error!(format!("Cannot decode Base64 of {file_name} cause {e}")),
+                )
+            })?;
+    }
+
+    if options.skip_leading_comments {
+        blank_leading_comments(&mut bytes);
+    }
+
+    // In YAML a leading `---` starts the document itself
+    let frontmatter = if extension == "yaml" || extension == "yml" {
+        Vec::new()
+    } else {
+        take_frontmatter(&mut bytes, file)?
+    };
+
+    let mut value = decode(&bytes, file, extension, options)?;
+
+    resolve_includes(
+        files,
+        &mut value,
+        file.parent().unwrap_or(path::Path::new("")),
+        &mut vec![file.to_owned()],
+        inputs,
+        options,
+    )?;
+
+    Ok((value, frontmatter))
+}
+
+/// Deserializes a JSON, TOML or YAML file, told by `extension`, handling keys
+/// repeated within an object as `key_collision_strategy` says
+#[cfg(feature = "json")]
+fn decode(
+    bytes: &[u8],
+    file: &path::Path,
+    // Only tells TOML and YAML apart from JSON
+    #[allow(unused)] extension: &ffi::OsStr,
+    options: &Options,
+) -> syn::Result<serde_json::Value> {
+    #[cfg(feature = "toml")]
+    if extension == "toml" {
+        return toml::from_slice(bytes, file, options.key_collision_strategy);
+    }
+
+    #[cfg(feature = "yaml")]
+    if extension == "yaml" || extension == "yml" {
+        return yaml::from_slice(bytes, file, options);
+    }
+
+    from_slice(bytes, file, options.key_collision_strategy)
+}
+
+/// Format of the locales with the given extension, `yml` being YAML like
+/// `yaml`, for the formats enabled
+fn locale_format(extension: &ffi::OsStr) -> Option<&'static str> {
+    match extension.to_str()? {
+        "json" => Some("json"),
+        "toml" if cfg!(feature = "toml") => Some("toml"),
+        "yaml" | "yml" if cfg!(feature = "yaml") => Some("yaml"),
+        _ => None,
+    }
+}
+
+/// Fails with an error per locale among `paths` in the format of `reference`
+/// whose keys or value shapes differ from those of `reference`, skipping
+/// sidecar files. Under `partial`, keys `reference` lacks are no difference
+#[cfg(feature = "json")]
+pub fn check_structure(
+    files: &dyn FileSystem,
+    reference: &path::Path,
+    paths: &[path::PathBuf],
+    options: &Options,
+) -> syn::Result<()> {
+    if reference.extension().and_then(locale_format).is_none() {
+        return Ok(());
+    }
+
+    let expected = parse_from_fs(files, reference.to_owned(), &shape_options(options))?;
+    let mut errors = Vec::new();
+
+    for locale in other_locales(files, reference, paths, options)? {
+        let mut diff = JsonNamespace::structure_diff(&expected.tokens, &locale.tokens);
+
+        // `partial` fills the keys only the other locale has
+        if options.partial {
+            diff.added.clear();
+        }
+
+        if diff.is_empty() {
+            continue;
+        }
+
+        let problems = [
+            ("missing keys", &diff.removed),
+            ("extra keys", &diff.added),
+            ("type mismatches", &diff.changed),
+        ]
+        .into_iter()
+        .filter(|(_, keys)| !keys.is_empty())
+        .map(|(kind, keys)| format!("{kind} {}", keys.join(", ")))
+        .collect::<Vec<_>>();
+
+        errors.push(syn::Error::new_spanned(
+            syn::LitStr::new(&locale.name, proc_macro2::Span::call_site()),
+            error!(format!(
+                "Locale {} differs in structure from {}: {}",
+                locale.name,
+                expected.name,
+                problems.join("; ")
+            )),
+        ));
+    }
+
+    let mut errors = errors.into_iter();
+
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|e| error.combine(e));
+            Err(error)
+        },
+        None => Ok(()),
+    }
+}
+
+/// Adds every key that another locale among `paths` in the format of
+/// `reference` has and `file` lacks, with its dotted path as the value under
+/// `fallback_to_key` and an empty string otherwise, warning about each under
+/// `partial`
+#[cfg(feature = "json")]
+pub fn fill_missing_keys(
+    files: &dyn FileSystem,
+    file: &mut File,
+    reference: &path::Path,
+    paths: &[path::PathBuf],
+) -> syn::Result<()> {
+    if reference.extension().and_then(locale_format).is_none() {
+        return Ok(());
+    }
+
+    let ctx = Context::new(&file.name, String::new(), &file.options);
+
+    for locale in other_locales(files, reference, paths, &file.options)? {
+        let mut filled = Vec::new();
+
+        file.tokens
+            .fill_missing(&locale.tokens, &mut Vec::new(), &ctx, &mut filled);
+
+        if file.options.partial {
+            let filling = if file.options.fallback_to_key {
+                "filled with its key"
+            } else {
+                "left empty"
+            };
+
+            file.warnings.extend(filled.into_iter().map(|key| {
+                format!(
+                    "Key {key} of {} is missing from {}, {filling}",
+                    locale.name, file.name
+                )
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the `markdown_doc` table of the keys of `file` with their values in
+/// it and in every other locale among `paths` in the format of `reference`
+#[cfg(feature = "json")]
+pub fn write_markdown_doc(
+    files: &dyn FileSystem,
+    file: &File,
+    reference: &path::Path,
+    paths: &[path::PathBuf],
+) -> syn::Result<()> {
+    let Some(doc) = &file.options.markdown_doc else {
+        return Ok(());
+    };
+    let others = if reference.extension().and_then(locale_format).is_some() {
+        other_locales(files, reference, paths, &file.options)?
+    } else {
+        Vec::new()
+    };
+
+    write_markdown(doc, [file].into_iter().chain(&others))
+}
+
+/// Options that only keep what changes how a locale is read and which keys and
+/// shapes it has, so exports and other side effects stay off
+#[cfg(feature = "json")]
+fn shape_options(options: &Options) -> Options {
+    Options {
+        encoding: options.encoding,
+        key_collision_strategy: options.key_collision_strategy,
+        locale_metadata_key: options.locale_metadata_key.clone(),
+        kv_array_mode: options.kv_array_mode,
+        skip_leading_comments: options.skip_leading_comments,
+        max_nesting_depth: options.max_nesting_depth,
+        #[cfg(feature = "regex")]
+        key_filter_regex: options.key_filter_regex.clone(),
+        #[cfg(feature = "regex")]
+        key_exclude_regex: options.key_exclude_regex.clone(),
+        ..Default::default()
+    }
+}
+
+/// The locales among `paths` other than `reference` and in its format, sorted
+/// by path and parsed with [`shape_options`]. Sidecar files are skipped
+#[cfg(feature = "json")]
+fn other_locales(
+    files: &dyn FileSystem,
+    reference: &path::Path,
+    paths: &[path::PathBuf],
+    options: &Options,
+) -> syn::Result<Vec<File>> {
+    const SIDECARS: [&str; 4] = ["info", "versions", "normalization", "compat"];
+
+    let format = reference.extension().and_then(locale_format);
+
+    let mut paths = paths.iter().collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .filter(|path| {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            let is_sidecar = stem
+                .rsplit_once('.')
+                .is_some_and(|(_, suffix)| SIDECARS.contains(&suffix))
+                || options
+                    .compat_aliases
+                    .as_deref()
+                    .is_some_and(|compat| path.file_name().is_some_and(|name| name == compat));
+
+            *path != reference
+                && files.is_file(path)
+                && path.extension().and_then(locale_format) == format
+                && !is_sidecar
+        })
+        .map(|path| parse_from_fs(files, path.clone(), &shape_options(options)))
+        .collect()
+}
+
+/// Reads the `key: value` lines of a YAML frontmatter between `---` lines at
+/// the start of the file and replaces it with spaces, keeping the line numbers
+/// of parse errors intact
+#[cfg(feature = "json")]
+fn take_frontmatter(bytes: &mut [u8], file: &path::Path) -> syn::Result<Vec<(String, String)>> {
+    let error = |message: String| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+            error!(format!("{message} in frontmatter of {}", file.display())),
+        )
+    };
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return Ok(Vec::new());
+    };
+    let mut lines = text.split_inclusive('\n');
+    let Some(first) = lines.next().filter(|line| line.trim_end() == "---") else {
+        return Ok(Vec::new());
+    };
+
+    let mut entries = Vec::new();
+    let mut end = None;
+    let mut offset = first.len();
+
+    for (number, line) in lines.enumerate() {
+        offset += line.len();
+
+        match line.trim() {
+            "---" => {
+                end = Some(offset);
+                break;
+            },
+            "" => {},
+            line if line.starts_with('#') => {},
+            line => {
+                let (key, value) = line.split_once(':').ok_or_else(|| {
+                    error(format!("Expected `key: value` on line {}", number + 2))
+                })?;
+                let value = value.trim();
+                let value = value
+                    .strip_prefix('"')
+                    .and_then(|value| value.strip_suffix('"'))
+                    .or_else(|| {
+                        value
+                            .strip_prefix('\'')
+                            .and_then(|value| value.strip_suffix('\''))
+                    })
+                    .unwrap_or(value);
+
+                entries.push((key.trim().to_owned(), value.to_owned()));
+            },
+        }
+    }
+
+    let end = end.ok_or_else(|| error("Missing closing `---`".to_owned()))?;
+
+    bytes[..end]
+        .iter_mut()
+        .filter(|byte| **byte != b'\n')
+        .for_each(|byte| *byte = b' ');
+
+    Ok(entries)
+}
+
+/// Replaces a UTF-8 BOM and the `// ja_JP` comment lines before the JSON with
+/// spaces, keeping the line numbers of parse errors intact
+#[cfg(feature = "json")]
+fn blank_leading_comments(bytes: &mut [u8]) {
+    let mut start = 0;
+
+    if bytes.starts_with("\u{feff}".as_bytes()) {
+        bytes[..3].fill(b' ');
+        start = 3;
+    }
+
+    while start < bytes.len() {
+        let end = bytes[start..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .map_or(bytes.len(), |newline| start + newline + 1);
+        let line = bytes[start..end].trim_ascii();
+
+        if line.starts_with(b"//") {
+            bytes[start..end]
+                .iter_mut()
+                .filter(|byte| **byte != b'\n')
+                .for_each(|byte| *byte = b' ');
+        } else if !line.is_empty() {
+            break;
+        }
+
+        start = end;
+    }
+}
+
+/// Compares the SHA-256 of `bytes` with the hex digest stored next to `file`
+/// in `<file>.sha256`
+#[cfg(feature = "checksum")]
+fn verify_checksum(files: &dyn FileSystem, file: &path::Path, bytes: &[u8]) -> syn::Result<()> {
+    use sha2::Digest;
+
+    let mut checksum_path = file.as_os_str().to_owned();
+    checksum_path.push(".sha256");
+    let checksum_path = path::PathBuf::from(checksum_path);
+
+    let expected = files.read_file(&checksum_path).map_err(|e| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&e.to_string(), proc_macro2::Span::call_site()),
+            error!(format!(
+                "Cannot read checksum file {} cause {e}",
+                checksum_path.display()
+            )),
+        )
+    })?;
+    // Accept both a bare digest and `sha256sum` output: `<digest>  <file>`
+    let expected = String::from_utf8_lossy(&expected)
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual = sha2::Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            syn::LitStr::new(&actual, proc_macro2::Span::call_site()),
+            error!(format!(
+                "Checksum mismatch for {}: expected {expected}, actually {actual}",
+                file.display()
+            )),
+        ))
+    }
+}
@@ -0,0 +1,77 @@
+//! TOML locales, converted to JSON values so they go through the JSON parser
+//! and code generation
+
+use super::*;
+
+/// Deserializes a TOML file as the JSON value the JSON parser expects. TOML
+/// forbids repeated keys, so only the default `collisions` is accepted
+pub(super) fn from_slice(
+    bytes: &[u8],
+    file: &path::Path,
+    collisions: KeyCollisionStrategy,
+) -> syn::Result<serde_json::Value> {
+    let error = |message: String| {
+        syn::Error::new_spanned(
+            syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+            error!(message),
+        )
+    };
+
+    if collisions != KeyCollisionStrategy::Error {
+        return Err(error(format!(
+            "key_collision_strategy does not apply to {}, TOML never allows a repeated key",
+            file.display()
+        )));
+    }
+
+    let text = std::str::from_utf8(bytes)
+        .map_err(|e| error(format!("Cannot deserialize {} cause {e}", file.display())))?;
+    let table = text
+        .parse::<::toml::Table>()
+        .map_err(|e| parse_error(file, text, &e))?;
+
+    Ok(to_json(::toml::Value::Table(table)))
+}
+
+/// Every TOML value as its JSON counterpart, dates and times as strings and
+/// floats JSON cannot hold (`nan`, `inf`) as well
+fn to_json(value: ::toml::Value) -> serde_json::Value {
+    match value {
+        ::toml::Value::String(s) => serde_json::Value::String(s),
+        ::toml::Value::Integer(i) => i.into(),
+        ::toml::Value::Float(f) => {
+            serde_json::Number::from_f64(f)
+                .map_or_else(|| serde_json::Value::String(f.to_string()), Into::into)
+        },
+        ::toml::Value::Boolean(b) => b.into(),
+        ::toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        ::toml::Value::Array(arr) => arr.into_iter().map(to_json).collect(),
+        ::toml::Value::Table(table) => {
+            serde_json::Value::Object(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, to_json(value)))
+                    .collect(),
+            )
+        },
+    }
+}
+
+/// Error for a file the TOML parser rejected, naming its absolute path and
+/// the position as `path:line:column` like the JSON one
+fn parse_error(file: &path::Path, text: &str, e: &::toml::de::Error) -> syn::Error {
+    let file = fs::canonicalize(file).unwrap_or_else(|_| file.to_owned());
+    let offset = e.span().map_or(0, |span| span.start);
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.chars().rev().take_while(|&c| c != '\n').count() + 1;
+
+    syn::Error::new_spanned(
+        syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+        error!(format!(
+            "Cannot deserialize {}:{line}:{column} cause {}",
+            file.display(),
+            e.message()
+        )),
+    )
+}
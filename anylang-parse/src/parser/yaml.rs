@@ -0,0 +1,157 @@
+//! YAML locales, converted to JSON values so they go through the JSON parser
+//! and code generation
+
+use super::*;
+
+/// Deserializes a YAML file as the JSON value the JSON parser expects, anchors,
+/// aliases and `<<` merge keys resolved and keys repeated within a mapping
+/// handled as `key_collision_strategy` says
+pub(super) fn from_slice(
+    bytes: &[u8],
+    file: &path::Path,
+    options: &Options,
+) -> syn::Result<serde_json::Value> {
+    use serde::de::DeserializeSeed;
+
+    let collisions = options.key_collision_strategy;
+    // Scalar keys such as `true:` or `1:` are read as written
+    let mut value = ValueSeed(collisions)
+        .deserialize(serde_yaml::Deserializer::from_slice(bytes))
+        .map_err(|e| parse_error(file, &e))?;
+
+    merge(&mut value, file)?;
+
+    // `kv_array_mode` reads the root sequence as `key`/`value` pairs instead
+    if !options.kv_array_mode {
+        merge_root_sequence(&mut value, file, collisions)?;
+    }
+
+    Ok(value)
+}
+
+/// Error pointing at `file`
+fn error(file: &path::Path, message: String) -> syn::Error {
+    syn::Error::new_spanned(
+        syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+        error!(message),
+    )
+}
+
+/// Resolves `<<` merge keys, keys written next to them winning
+fn merge(value: &mut serde_json::Value, file: &path::Path) -> syn::Result<()> {
+    match value {
+        serde_json::Value::Object(map) => {
+            if let Some(merged) = map.remove("<<") {
+                let sources = match merged {
+                    serde_json::Value::Array(arr) => arr,
+                    other => vec![other],
+                };
+
+                for mut source in sources {
+                    merge(&mut source, file)?;
+
+                    let serde_json::Value::Object(source) = source else {
+                        return Err(error(
+                            file,
+                            format!(
+                                "Expected a mapping or a sequence of mappings after `<<` in {}",
+                                file.display()
+                            ),
+                        ));
+                    };
+
+                    for (key, val) in source {
+                        map.entry(key).or_insert(val);
+                    }
+                }
+            }
+
+            for val in map.values_mut() {
+                merge(val, file)?;
+            }
+        },
+        serde_json::Value::Array(arr) => {
+            for val in arr.iter_mut() {
+                merge(val, file)?;
+            }
+        },
+        _ => (),
+    }
+
+    Ok(())
+}
+
+/// Merges a root sequence of mappings into one mapping, like the JSON parser
+/// does with a root array, a key repeated across them handled as `collisions`
+/// says
+fn merge_root_sequence(
+    value: &mut serde_json::Value,
+    file: &path::Path,
+    collisions: KeyCollisionStrategy,
+) -> syn::Result<()> {
+    let serde_json::Value::Array(arr) = value else {
+        return Ok(());
+    };
+
+    if arr.is_empty() || !arr.iter().all(serde_json::Value::is_object) {
+        return Ok(());
+    }
+
+    let mut object = serde_json::Map::new();
+
+    for mapping in std::mem::take(arr) {
+        let serde_json::Value::Object(mapping) = mapping else {
+            unreachable!("every item was checked to be a mapping");
+        };
+
+        for (key, val) in mapping {
+            match (object.contains_key(&key), collisions) {
+                (true, KeyCollisionStrategy::Error) => {
+                    return Err(error(
+                        file,
+                        format!(
+                            "Key '{key}' appears in several mappings of one sequence in {}, set \
+                             key_collision_strategy to \"first_wins\" or \"last_wins\" to keep \
+                             one of the values",
+                            file.display()
+                        ),
+                    ));
+                },
+                (true, KeyCollisionStrategy::FirstWins) => (),
+                _ => {
+                    object.insert(key, val);
+                },
+            }
+        }
+    }
+
+    *value = serde_json::Value::Object(object);
+
+    Ok(())
+}
+
+/// Error for a file the YAML parser rejected, naming its absolute path and
+/// the position as `path:line:column` like the JSON one
+fn parse_error(file: &path::Path, e: &serde_yaml::Error) -> syn::Error {
+    let file = fs::canonicalize(file).unwrap_or_else(|_| file.to_owned());
+    // The position moves into the path, where editors pick it up
+    let (message, position) = match e.location() {
+        Some(location) => {
+            let (line, column) = (location.line(), location.column());
+            let message =
+                e.to_string()
+                    .replacen(&format!(" at line {line} column {column}"), "", 1);
+
+            (message, format!(":{line}:{column}"))
+        },
+        None => (e.to_string(), String::new()),
+    };
+
+    syn::Error::new_spanned(
+        syn::LitStr::new(&file.display().to_string(), proc_macro2::Span::call_site()),
+        error!(format!(
+            "Cannot deserialize {}{position} cause {message}",
+            file.display()
+        )),
+    )
+}
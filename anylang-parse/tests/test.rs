@@ -0,0 +1,356 @@
+use anylang_parse::{FileSystem, Options};
+use std::{collections::BTreeMap, io, path};
+
+/// Locale files held in memory
+#[derive(Default)]
+struct MockFileSystem(BTreeMap<path::PathBuf, &'static str>);
+
+impl MockFileSystem {
+    fn with(mut self, path: &str, content: &'static str) -> Self {
+        self.0.insert(path.into(), content);
+        self
+    }
+}
+
+impl FileSystem for MockFileSystem {
+    fn read_dir(&self, dir: &path::Path) -> io::Result<Vec<path::PathBuf>> {
+        Ok(self
+            .0
+            .keys()
+            .filter(|path| path.parent() == Some(dir))
+            .cloned()
+            .collect())
+    }
+
+    fn read_file(&self, path: &path::Path) -> io::Result<Vec<u8>> {
+        self.0
+            .get(path)
+            .map(|content| content.as_bytes().to_vec())
+            .ok_or_else(|| io::ErrorKind::NotFound.into())
+    }
+}
+
+#[test]
+fn check_truncate_at_zero() {
+    let err = syn::parse_str::<Options>(", truncate_at = 0")
+        .err()
+        .expect("truncate_at = 0 must be rejected");
+
+    assert!(err.to_string().contains("truncate_at must be at least 1"));
+    assert_eq!(
+        syn::parse_str::<Options>(", truncate_at = 1")
+            .unwrap()
+            .truncate_at,
+        Some(1)
+    );
+}
+
+#[cfg(feature = "json")]
+fn namespace(value: serde_json::Value, options: &Options) -> anylang_parse::JsonNamespace {
+    let mut ctx = anylang_parse::Context::new("en_US", "en_US.json".to_owned(), options);
+    let mut root = anylang_parse::JsonNamespace::default();
+
+    anylang_parse::parse_json(&value, &mut root, &mut ctx).unwrap();
+    root
+}
+
+/// Warnings parsing `value` as the locale `name` with `options` gives
+#[cfg(feature = "json")]
+fn warnings(name: &str, value: serde_json::Value, options: &str) -> Vec<String> {
+    let options = syn::parse_str::<Options>(options).unwrap();
+    let mut ctx = anylang_parse::Context::new(name, format!("{name}.json"), &options);
+
+    anylang_parse::parse_json(&value, &mut Default::default(), &mut ctx).unwrap();
+    ctx.warnings().to_vec()
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_locale_diff() {
+    use anylang_parse::JsonNamespace;
+    use serde_json::json;
+
+    let options = Options::default();
+    let old = namespace(
+        json!({
+            "ping": "pong",
+            "gone": "bye",
+            "dummy": {"foo": "buzz", "some": ["none", "or"], "one": "single"}
+        }),
+        &options,
+    );
+    let new = namespace(
+        json!({
+            "ping": "pong!",
+            "fresh": "hi",
+            "dummy": {"foo": "buzz", "some": "none", "one": ["single"]}
+        }),
+        &options,
+    );
+
+    let diff = JsonNamespace::diff(&old, &new);
+
+    assert_eq!(diff.added, ["fresh"]);
+    assert_eq!(diff.removed, ["gone"]);
+    assert_eq!(diff.changed, ["dummy.one", "dummy.some", "ping"]);
+    assert!(!diff.is_empty());
+    assert!(JsonNamespace::diff(&old, &old).is_empty());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn check_toml_rejects_key_collision_strategy() {
+    let file = concat!(env!("CARGO_MANIFEST_DIR"), "/../tests/lang/toml/ru_RU.toml");
+    let options = syn::parse_str::<Options>(", key_collision_strategy = \"last_wins\"").unwrap();
+    let err = anylang_parse::parse_from_file(file.into(), &options)
+        .err()
+        .expect("TOML never repeats a key");
+
+    assert!(
+        err.to_string()
+            .contains("key_collision_strategy does not apply to")
+    );
+    assert!(anylang_parse::parse_from_file(file.into(), &Options::default()).is_ok());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_mock_file_system() {
+    let files = MockFileSystem::default()
+        .with(
+            "lang/en_US.json",
+            r#"{"ping": "pong", "$include": "shared/base.json"}"#,
+        )
+        .with("lang/shared/base.json", r#"{"dummy": {"foo": "buzz"}}"#)
+        .with("lang/en_US.info.json", r#"{"language": "English"}"#)
+        .with("lang/de_DE.json", r#"{"ping": "pong", "extra": "mehr"}"#);
+    let reference = path::Path::new("lang/en_US.json");
+    let paths = files.read_dir(path::Path::new("lang")).unwrap();
+    let options = syn::parse_str::<Options>(", fallback_to_key = true").unwrap();
+
+    let mut file = anylang_parse::parse_from_fs(&files, reference.into(), &options).unwrap();
+
+    let err = anylang_parse::check_structure(&files, reference, &paths, &options)
+        .expect_err("de_DE has a key en_US lacks");
+    assert!(err.to_string().contains("extra keys extra"), "{err}");
+
+    anylang_parse::fill_missing_keys(&files, &mut file, reference, &paths).unwrap();
+
+    let code = proc_macro2::TokenStream::from(file).to_string();
+
+    assert!(code.contains(r#"PING : & 'static str = "pong""#), "{code}");
+    assert!(code.contains(r#"FOO : & 'static str = "buzz""#), "{code}");
+    assert!(code.contains(r#"LANGUAGE : & str = "English""#), "{code}");
+    assert!(
+        code.contains(r#"EXTRA : & 'static str = "extra""#),
+        "{code}"
+    );
+    assert!(anylang_parse::parse_from_fs(&files, "lang/fr_FR.json".into(), &options).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_mock_file_system_rs() {
+    let files = MockFileSystem::default().with(
+        "lang/en_US.rs",
+        r#"pub mod lang { pub const PING: &str = "pong"; }"#,
+    );
+    let options = Options::default();
+
+    let file = anylang_parse::parse_from_fs(&files, "lang/en_US.rs".into(), &options).unwrap();
+    let code = proc_macro2::TokenStream::from(file).to_string();
+
+    assert_eq!(code, r#"include ! ("lang/en_US.rs") ;"#);
+    assert!(anylang_parse::parse_from_fs(&files, "lang/fr_FR.rs".into(), &options).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_partial_warnings() {
+    let en_us = r#"{"ping": "pong", "menu": {"quit": "Quit"}}"#;
+    let fr_fr = r#"{"ping": "pong"}"#;
+
+    let code = fill_fr_fr(en_us, fr_fr, ", partial = true").unwrap();
+    assert!(code.contains(r#"QUIT : & 'static str = """#), "{code}");
+    assert_eq!(code.matches("is missing from").count(), 1, "{code}");
+    assert!(
+        code.contains("Key menu.quit of en_US is missing from fr_FR, left empty"),
+        "{code}"
+    );
+
+    let code = fill_fr_fr(en_us, fr_fr, ", partial = true, fallback_to_key = true").unwrap();
+    assert!(
+        code.contains(r#"QUIT : & 'static str = "menu.quit""#),
+        "{code}"
+    );
+    assert!(
+        code.contains("Key menu.quit of en_US is missing from fr_FR, filled with its key"),
+        "{code}"
+    );
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_partial_strict_structure() {
+    let en_us = r#"{"ping": "pong", "menu": {"quit": "Quit"}}"#;
+
+    // The keys fr_FR lacks are filled, those only it has still fail
+    check_fr_fr(en_us, r#"{"ping": "pong"}"#, ", partial = true").unwrap();
+    let err = check_fr_fr(
+        en_us,
+        r#"{"ping": "pong", "extra": "plus"}"#,
+        ", partial = true",
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(err.contains("missing keys extra"), "{err}");
+    assert!(!err.contains("menu.quit"), "{err}");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_warn_non_ascii_keys() {
+    use serde_json::json;
+
+    let options = ", warn_non_ascii_keys = true";
+
+    assert_eq!(
+        warnings("ja_JP", json!({"menu": {"挨拶": "こんにちは"}}), options),
+        [
+            "Key 'menu.挨拶' in ja_JP.json is not ASCII, which many tools handle poorly in \
+             identifiers; consider an ASCII key"
+        ]
+    );
+    assert!(warnings("ja_JP", json!({"greeting": "こんにちは"}), options).is_empty());
+    assert!(warnings("ja_JP", json!({"挨拶": "こんにちは"}), "").is_empty());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_warn_string_len() {
+    use serde_json::json;
+
+    let options = ", warn_string_len = 8";
+
+    assert_eq!(
+        warnings("en_BZ", json!({"intro": ["Hi", "Welcome aboard"]}), options),
+        [
+            "Key 'intro' in en_BZ.json is 14 characters long, over warn_string_len (8); consider \
+             a shorter translation"
+        ]
+    );
+    assert!(warnings("en_BZ", json!({"intro": ["Hi", "Welcome!"]}), options).is_empty());
+}
+
+#[cfg(feature = "bidi")]
+#[test]
+fn check_verify_rtl_markers() {
+    use serde_json::json;
+
+    let options = ", verify_rtl_markers = true";
+
+    assert_eq!(
+        warnings("he_IL", json!({"version": "גרסה 2.0 beta"}), options),
+        [
+            "Key 'version' in he_IL.json mixes left-to-right and right-to-left text without \
+             directional marks; wrap the embedded run in RLM/LRM or an isolate"
+        ]
+    );
+    assert!(
+        warnings(
+            "he_IL",
+            json!({"version": "גרסה \u{2066}2.0 beta\u{2069}"}),
+            options
+        )
+        .is_empty()
+    );
+    // Left-to-right locales may quote right-to-left text as they like
+    assert!(warnings("en_US", json!({"version": "גרסה 2.0 beta"}), options).is_empty());
+}
+
+/// `lang/en_US.json` and `lang/fr_FR.json` in memory, with the paths of the
+/// directory
+#[cfg(feature = "json")]
+fn en_us_fr_fr(en_us: &'static str, fr_fr: &'static str) -> (MockFileSystem, Vec<path::PathBuf>) {
+    let files = MockFileSystem::default()
+        .with("lang/en_US.json", en_us)
+        .with("lang/fr_FR.json", fr_fr);
+    let paths = files.read_dir(path::Path::new("lang")).unwrap();
+
+    (files, paths)
+}
+
+/// Generated code of `lang/fr_FR.json` with the keys `lang/en_US.json` adds
+/// filled in
+#[cfg(feature = "json")]
+fn fill_fr_fr(en_us: &'static str, fr_fr: &'static str, options: &str) -> syn::Result<String> {
+    let (files, paths) = en_us_fr_fr(en_us, fr_fr);
+    let reference = path::Path::new("lang/fr_FR.json");
+    let options = syn::parse_str::<Options>(options).unwrap();
+
+    let mut file = anylang_parse::parse_from_fs(&files, reference.into(), &options)?;
+    anylang_parse::fill_missing_keys(&files, &mut file, reference, &paths)?;
+
+    Ok(proc_macro2::TokenStream::from(file).to_string())
+}
+
+/// Checks `lang/en_US.json` against the structure of `lang/fr_FR.json`
+#[cfg(feature = "json")]
+fn check_fr_fr(en_us: &'static str, fr_fr: &'static str, options: &str) -> syn::Result<()> {
+    let (files, paths) = en_us_fr_fr(en_us, fr_fr);
+    let options = syn::parse_str::<Options>(options).unwrap();
+
+    anylang_parse::check_structure(&files, path::Path::new("lang/fr_FR.json"), &paths, &options)
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn check_shape_options() {
+    let en_us = "// en_US\n{\"ping\": \"pong\", \"quit\": \"Quit\"}";
+    let fr_fr = "// fr_FR\n{\"ping\": \"pong\"}";
+    let options = ", skip_leading_comments = true, fallback_to_key = true";
+
+    let code = fill_fr_fr(en_us, fr_fr, options).unwrap();
+    assert!(code.contains(r#"QUIT : & 'static str = "quit""#), "{code}");
+    let err = check_fr_fr(en_us, fr_fr, options).unwrap_err().to_string();
+    assert!(err.contains("extra keys quit"), "{err}");
+
+    let en_us = r#"[{"key": "ping", "value": "pong"}, {"key": "quit", "value": "Quit"}]"#;
+    let fr_fr = r#"[{"key": "ping", "value": "pong"}]"#;
+    let options = ", kv_array_mode = true, fallback_to_key = true";
+
+    let code = fill_fr_fr(en_us, fr_fr, options).unwrap();
+    assert!(code.contains(r#"QUIT : & 'static str = "quit""#), "{code}");
+    let err = check_fr_fr(en_us, fr_fr, options).unwrap_err().to_string();
+    assert!(err.contains("extra keys quit"), "{err}");
+
+    let en_us = r#"{"ping": "pong", "menu": {"file": {"quit": "Quit"}}}"#;
+    let fr_fr = r#"{"ping": "pong"}"#;
+    let options = ", max_nesting_depth = 1, fallback_to_key = true";
+
+    for err in [
+        fill_fr_fr(en_us, fr_fr, options).unwrap_err(),
+        check_fr_fr(en_us, fr_fr, options).unwrap_err(),
+    ] {
+        assert!(
+            err.to_string().contains("exceeds max_nesting_depth (1)"),
+            "{err}"
+        );
+    }
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn check_shape_options_key_regex() {
+    let en_us = r#"{"ping": "pong", "secret": "hunter2"}"#;
+    let fr_fr = r#"{"ping": "pong"}"#;
+
+    for options in [
+        r#", key_exclude_regex = "^secret$", fallback_to_key = true"#,
+        r#", key_filter_regex = "^ping$", fallback_to_key = true"#,
+    ] {
+        let code = fill_fr_fr(en_us, fr_fr, options).unwrap();
+        assert!(!code.contains("SECRET"), "{code}");
+        check_fr_fr(en_us, fr_fr, options).unwrap();
+    }
+}
@@ -1,8 +1,7 @@
 //! # AnyLang - Static Localization for Rust
 //!
 //! A Rust proc-macro crate for embedding localization files directly into your
-//! binary at compile time. Supports JSON format with TOML support planned for
-//! future releases.
+//! binary at compile time. Supports JSON, TOML and YAML formats.
 //!
 //! ## Features
 //!
@@ -10,7 +9,7 @@
 //! - **Type-safe** - Full Rust type checking for all localized strings
 //! - **Hierarchical organization** - Nested JSON objects become nested Rust
 //!   modules
-//! - **Multi-format support** - JSON with TOML coming soon
+//! - **Multi-format support** - JSON, TOML and YAML
 //! - **Flexible data types** - Supports strings, numbers, booleans, arrays, and
 //!   null values
 //!
@@ -23,6 +22,22 @@
 //! anylang = { version = "0.1", features = ["json"] }
 //! ```
 //!
+//! ### Cargo features
+//!
+//! - `json` *(default)* - enables `include_json_dir!`
+//! - `file_locking` - takes a shared advisory lock (via `fs2`) on every locale
+//!   file while it is read, so a file that is being rewritten by a dev server
+//!   is never parsed half-written
+//! - `checksum` - enables the `checksum_file` option
+//! - `base64` - enables `encoding = "base64"`
+//! - `normalization` - enables the `normalization_file` option
+//! - `regex` - enables the `key_filter_regex` and `key_exclude_regex` options
+//! - `transliterate` - enables the `transliterate` option
+//! - `ron` - enables the `ron_output` option
+//! - `toml` - enables `include_toml_dir!`
+//! - `yaml` - enables `include_yaml_dir!`
+//! - `bidi` - enables the `verify_rtl_markers` option
+//!
 //! ## Usage
 //!
 //! ### Basic JSON Localization
@@ -76,6 +91,75 @@
 //! }
 //! ```
 //!
+//! Files are matched by their full stem, so a locale code may contain dots:
+//! `include_json_dir!("./lang", "en.UK")` loads `en.UK.json`. A root value in
+//! such a file is named with the dots replaced by underscores (`EN_UK`).
+//!
+//! The directory may also be built with `concat!` and `env!`, e.g. to load
+//! files that a `build.rs` generated:
+//! `include_json_dir!(concat!(env!("OUT_DIR"), "/lang"), "en_US")`.
+//!
+//! The macro can be called from your own `macro_rules!` to pick the locale in
+//! one place; `$lang:literal` and `$lang:expr` fragments are accepted like
+//! inline literals:
+//!
+//! ```ignore
+//! macro_rules! include_locale {
+//!     ($lang:literal) => {
+//!         anylang::include_json_dir!("./lang", $lang);
+//!     };
+//! }
+//!
+//! include_locale!("en_US");
+//! ```
+//!
+//! ### TOML Localization
+//!
+//! With the `toml` feature, `include_toml_dir!` reads `.toml` locale files the
+//! same way, taking the same options, `$include` and frontmatter included.
+//! Tables become modules, and dates and times are kept as written:
+//!
+//! ```toml
+//! ping = "понг"
+//! released = 2015-05-15
+//!
+//! [rust.good]
+//! true = [1, true]
+//! ```
+//!
+//! ```ignore
+//! anylang::include_toml_dir!("./lang", "ru_RU");
+//!
+//! assert_eq!(lang::RELEASED, "2015-05-15");
+//! assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+//! ```
+//!
+//! ### YAML Localization
+//!
+//! With the `yaml` feature, `include_yaml_dir!` reads `.yaml` and `.yml` locale
+//! files, as written for Rails or i18next, taking the same options, `$include`
+//! included. Anchors, aliases and `<<` merge keys are resolved, and a root
+//! sequence of mappings is merged into one module like a root array in JSON, or
+//! read as `key`/`value` pairs under `kv_array_mode`:
+//!
+//! ```yaml
+//! ping: понг
+//! dummy: &dummy
+//!   foo: базз
+//! copy: *dummy
+//! rust:
+//!   rust: раст
+//!   good:
+//!     true: [1, true]
+//! ```
+//!
+//! ```ignore
+//! anylang::include_yaml_dir!("./lang", "ru_RU");
+//!
+//! assert_eq!(lang::copy::FOO, "базз");
+//! assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+//! ```
+//!
 //! ## Support for non-string types
 //!
 //! There is also support for all standard JSON types. Examples below
@@ -158,6 +242,155 @@
 //! ]
 //! ```
 //!
+//! ## File Composition
+//!
+//! An object may pull in the keys of another JSON, TOML or YAML file, told by
+//! its extension, with `$include`. The path is relative to the file containing
+//! it, and keys written next to `$include` override the included ones:
+//!
+//! ```json
+//! {
+//!     "$include": "shared/base.json",
+//!     "ping": "pong"
+//! }
+//! ```
+//!
+//! ## Conditional Keys
+//!
+//! Objects under a `_cfg_<condition>` key are only compiled when the condition
+//! holds, and same-named keys outside of them become the fallback for every
+//! other target. Conditions are `cfg` flags (`_cfg_unix`, `_cfg_windows`,
+//! `_cfg_test`, `_cfg_debug_assertions`) or `<name>_<value>` pairs for
+//! `target_os`, `target_arch`, `target_family`, `target_env`, `target_vendor`,
+//! `target_endian`, `target_pointer_width` and `feature`:
+//!
+//! ```json
+//! {
+//!     "_cfg_target_os_ios": {
+//!         "back_button": "Back"
+//!     },
+//!     "back_button": "←"
+//! }
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore
+//! #[cfg(target_os = "ios")]
+//! pub const BACK_BUTTON: &str = "Back";
+//! #[cfg(not(any(target_os = "ios")))]
+//! pub const BACK_BUTTON: &str = "←";
+//! ```
+//!
+//! ## Context Annotations
+//!
+//! A `_context` string in an object describes it for translators and IDEs. It
+//! is not emitted as a constant but as the documentation of the generated
+//! module:
+//!
+//! ```json
+//! {
+//!     "_context": "navigation menu",
+//!     "back": "Back"
+//! }
+//! ```
+//!
+//! becomes `#[doc = "Context: navigation menu"] pub mod lang { ... }`.
+//!
+//! ## Locale Metadata
+//!
+//! An `en_US.info.json` next to `en_US.json` is loaded automatically and its
+//! string values become constants of a `meta` module inside the generated one:
+//!
+//! ```json
+//! {
+//!     "language": "English",
+//!     "region": "United States",
+//!     "direction": "ltr"
+//! }
+//! ```
+//!
+//! becomes `pub mod meta { pub const LANGUAGE: &str = "English"; ... }`, with
+//! `direction` named `TEXT_DIRECTION`.
+//!
+//! A YAML frontmatter of `key: value` lines between `---` lines at the start of
+//! a locale file, as some tools write, ends up in the same module; values of
+//! the `.info.json` file win over it:
+//!
+//! ```text
+//! ---
+//! description: Kenyan Swahili strings
+//! author: Amina
+//! ---
+//! {
+//!     "hello": "Habari"
+//! }
+//! ```
+//!
+//! With `locale_metadata_key = "_locale"`, the object under that top-level key
+//! of the locale file is read into the module instead of becoming constants:
+//!
+//! ```json
+//! {
+//!     "_locale": { "language": "English", "direction": "ltr" },
+//!     "ping": "pong"
+//! }
+//! ```
+//!
+//! ## Module Placement
+//!
+//! The macro expands to a bare `pub mod lang { ... }` item right where it is
+//! invoked, so the module lands in whatever module contains the call. To put
+//! the strings under `crate::i18n`, invoke the macro inside that module:
+//!
+//! ```ignore
+//! mod i18n {
+//!     anylang::include_json_dir!("./lang", "en_US");
+//! }
+//!
+//! fn main() {
+//!     assert_eq!(i18n::lang::PING, "pong");
+//! }
+//! ```
+//!
+//! Each locale needs its own enclosing module when several are loaded, since
+//! every invocation emits a module named `lang`:
+//!
+//! ```ignore
+//! mod en {
+//!     anylang::include_json_dir!("./lang", "en_US");
+//! }
+//!
+//! mod ru {
+//!     anylang::include_json_dir!("./lang", "ru_RU");
+//! }
+//! ```
+//!
+//! ## Build Scripts
+//!
+//! The parsing and code generation live in the `anylang-parse` crate, which the
+//! macros wrap. It can be used outside of a proc-macro, e.g. to generate the
+//! same code from a `build.rs`:
+//!
+//! ```ignore
+//! let options = anylang_parse::Options::default();
+//! let file = anylang_parse::parse_from_file("lang/en_US.json".into(), &options)?;
+//! let code = proc_macro2::TokenStream::from(file).to_string();
+//! ```
+//!
+//! ## Rust Locales
+//!
+//! A locale can also be written by hand as a Rust module and loaded with
+//! `include!` semantics, the path being relative to the crate root:
+//!
+//! ```ignore
+//! // lang/en_US.rs: pub mod lang { pub const PING: &str = "pong"; }
+//! anylang::include_rs_locale!("./lang/en_US.rs");
+//! ```
+//!
+//! `include_json_dir!` includes such `.rs` files too when they are the best
+//! match for the language, see `locale_priority`.
+//!
 //! ## Type Conversion
 //!
 //! All JSON types are automatically converted to Rust string types:
@@ -169,6 +402,8 @@
 //! - **Array** → `[&'static str; N]`
 //! - **Object** → Rust module with constants
 //!
+//! Windows line endings (`\r\n`) inside strings are normalized to `\n`.
+//!
 //! ## Naming Convention
 //!
 //! JSON keys are converted to SCREAMING_SNAKE_CASE for Rust constants:
@@ -176,22 +411,324 @@
 //! - `"foo_bar"` becomes `FOO_BAR`
 //! - `"some_key"` becomes `SOME_KEY`
 //!
+//! ## Options
+//!
+//! Extra `key = value` arguments can follow the language to tweak the output:
+//!
+//! ```ignore
+//! include_json_dir!("./lang", "en_US", append_file_name = true);
+//! ```
+//!
+//! - `append_file_name = true` - prefixes every constant with the file stem, so
+//!   `ping` in `en_US.json` becomes `EN_US__PING`
+//! - `pluralize_suffix = true` - for sibling keys ending in CLDR plural
+//!   suffixes (`items_zero`, `items_one`, `items_other`, ...) emits `pub const
+//!   fn items_plural(n: u64) -> &'static str` picking the form by English
+//!   plural rules; a group needs an `_other` form
+//! - `kv_array_mode = true` - reads a root array of `{"key": "ping", "value":
+//!   "pong"}` pairs as if it were an object
+//! - `no_alloc = true` - packs every string into a single NUL-separated `static
+//!   LOCALE_DATA: [u8; N]` buffer and emits a `const fn ping() -> &'static str`
+//!   accessor per key instead of a constant; arrays stay constants
+//! - `generate_trait = true` - adds `pub trait Locale` with a method per key,
+//!   named after its lowercased path (`dummy.foo` becomes `fn dummy_foo(&self)
+//!   -> &str`), and a `StaticLocale` implementing it with the generated values,
+//!   so tests can substitute their own implementation
+//! - `wasm_export = true` - adds a `lang::wasm` module, compiled only for
+//!   `target_arch = "wasm32"`, with a `#[wasm_bindgen]` getter per key named
+//!   after its lowercased path (`dummy_foo()`); your crate needs `wasm-bindgen`
+//!   as a dependency
+//! - `null_behavior = "empty" | "error"` - `"empty"` *(default)* turns `null`
+//!   into an empty string, `"error"` rejects every `null` value with a compile
+//!   error naming the key and the file
+//! - `transform = "uppercase" | "lowercase" | "trim" | "title_case"` - rewrites
+//!   every string value at compile time before it is emitted
+//! - `name_transform = "screaming_snake" | "screaming_kebab" | "lower_snake" |
+//!   "lower_camel" | "upper_camel" | "kebab"` - splits keys into words on `_`,
+//!   `-`, spaces and camelCase humps and joins them in the given case; kebab
+//!   variants use `__` as the separator since identifiers cannot contain `-`
+//!   (`btn-ok` becomes `BTN__OK`). Without it a key is simply uppercased
+//! - `generate_tests = true` - adds a `#[cfg(test)] mod tests` to the generated
+//!   module with a `test_<lang>_<key path>_non_empty` test per string constant,
+//!   guarding against accidentally emptied translations
+//! - `escape_html = true` - HTML-escapes `&`, `<`, `>`, `"` and `'` in every
+//!   string value at compile time
+//! - `checksum_file = true` *(`checksum` feature)* - requires a `<file>.sha256`
+//!   next to the locale file holding the hex SHA-256 of its contents (bare or
+//!   in `sha256sum` format) and fails the build when it does not match
+//! - `strict_typed_arrays = true` - rejects arrays whose elements are not all
+//!   of the same JSON type, such as `[1, "two", 3]`
+//! - `compat_aliases = "en_US.compat.json"` - reads a `{"old_key": "new.key"}`
+//!   file from the locale directory and emits a `#[deprecated]` `OLD_KEY`
+//!   constant equal to the renamed one, placed next to it, to keep old names
+//!   compiling during a migration
+//! - `generate_enum = true` - adds `pub enum LangKeys` with a variant per key
+//!   (`dummy.foo` becomes `LangKeys::DummyFoo`), `as_str()` returning the
+//!   dotted key path and `all()` listing every variant
+//! - `min_string_len = 1` - rejects any string shorter than the given number of
+//!   characters with a compile error naming the key and its length
+//! - `json_schema_export = "target/locale_schema.json"` - writes a JSON Schema
+//!   of the locale structure to the given path, relative to the crate root, so
+//!   that translators can validate their files against it
+//! - `truncate_at = 20` - cuts every string longer than the given number of
+//!   characters, at least 1, down to that length, the last character being `…`
+//! - `csv_report = "target/en_US.csv"` - writes every key path and its value as
+//!   RFC 4180 CSV (`key,value` header, array items as `key.0`, `key.1`, ...)
+//!   for translators working in spreadsheets
+//! - `gettext_pot = "locale/en_US.pot"` - writes a gettext template with a
+//!   `msgid` per key path and an empty `msgstr`, the current value kept as a
+//!   `#.` comment, for Poedit, Transifex and other gettext tools
+//! - `deduplicate_strings = true` - a constant repeating the value of an
+//!   earlier one is defined as that constant (`pub const DISMISS: &str =
+//!   CANCEL;`) instead of another copy of the literal
+//! - `deprecate_since = "2.0.0"` - reads `<file>.versions.json` next to the
+//!   locale, mapping key paths to the version they were added in
+//!   (`{"dummy.foo": "2.3.0"}`), and marks keys added after the given version
+//!   `#[deprecated(since = "2.3.0")]`
+//! - `typescript_types = "src/lang.d.ts"` - writes TypeScript declarations
+//!   mirroring the generated modules (`export namespace dummy { export const
+//!   FOO: string; }`) for front-end code consuming the same locale files
+//! - `encoding = "utf8" | "base64"` - `"base64"` decodes every file from Base64
+//!   before parsing it, for CMS exports delivered that way; needs the `base64`
+//!   feature
+//! - `force_rerun = true` - makes the macro expand again on every build, even
+//!   if no locale file changed, for hot-reload workflows. It includes
+//!   `$OUT_DIR/anylang_timestamp`, which your `build.rs` has to rewrite each
+//!   time:
+//!
+//!   ```ignore
+//!   fn main() {
+//!       let out_dir = std::env::var("OUT_DIR").unwrap();
+//!       let now = std::time::SystemTime::now()
+//!           .duration_since(std::time::UNIX_EPOCH)
+//!           .unwrap();
+//!
+//!       let path = format!("{out_dir}/anylang_timestamp");
+//!
+//!       std::fs::write(path, now.as_nanos().to_string()).unwrap();
+//!   }
+//!   ```
+//!
+//!   Without a `cargo:rerun-if-changed` line the build script itself runs on
+//!   every build. This slows down CI, so keep it to development builds
+//! - `validate_bcp47 = true` - rejects a language argument that is not a BCP 47
+//!   tag in canonical case (`en_US`, `en-US`, `zh_Hant_TW`, `es_419`), so typos
+//!   such as `"english"` or `"EN"` fail to compile
+//! - `stable_hash = "target/anylang_hash.txt"` - stores a hash of the generated
+//!   code in the given file on the first build and warns on later builds whose
+//!   output differs, printing the new hash to store. Useful for CI to notice
+//!   locale changes
+//! - `locale_negotiation = true` - when there is no file for the language,
+//!   tries ever shorter tags as in RFC 4647 lookup: `zh-Hant-TW` falls back to
+//!   `zh_Hant` and then `zh`. `-` and `_` are interchangeable in the tag and
+//!   the file names
+//! - `string_interning_mode = true` - collects every distinct string into one
+//!   `static POOL: [&str; N]` and defines each constant as an element of it
+//!   (`pub const PING: &str = POOL[0];`), keeping the data in one contiguous
+//!   array; arrays stay constants
+//! - `validate_no_trailing_spaces = true` - rejects any string ending with
+//!   spaces or other Unicode whitespace with a compile error naming the key and
+//!   how many such characters it ends with
+//! - `locale_priority = ["yaml", "json"]` - picks among several files named
+//!   after the language by extension, in the given order; by default `.json`
+//!   wins and other extensions follow by file name. Picking a file no reader
+//!   handles, such as `.txt`, fails the build
+//! - `required_keys = ["ping", "dummy.foo"]` - fails to compile, with an error
+//!   per path, when any of the listed keys or objects is missing from the
+//!   locale
+//! - `normalization_file = true` - reads `<file>.normalization.json` next to
+//!   the locale, mapping key paths to a Unicode normalization form
+//!   (`{"greeting": "NFC", "technical_term": "NFKC"}`), and normalizes those
+//!   values accordingly; keys not listed are left as written. Needs the
+//!   `normalization` feature
+//! - `warn_non_ascii_keys = true` - emits a compile warning for every key with
+//!   non-ASCII characters, since the resulting identifiers trip up many tools
+//! - `sanitize_html = true` - strips HTML tags and comments from every string
+//!   value at compile time, so `"Hello, <b>World</b>!"` becomes `"Hello,
+//!   World!"`; a `<` not followed by a letter, `/` or `!` is kept
+//! - `strict_structure = true` - also parses every other locale of the
+//!   directory in the same format and fails with an error per locale whose keys
+//!   differ from the included one (missing keys, extra keys, or a string where
+//!   an array is expected); `.info`, `.versions`, `.normalization` and
+//!   `.compat` sidecars are skipped
+//! - `const_ptr = true` - emits `PING_PTR: *const u8` and `PING_LEN: usize`
+//!   next to every string constant `PING` for C FFI, where strings are passed
+//!   as a pointer and length pair; the pointer stays valid for the whole
+//!   program
+//! - `module_attrs = ["allow(dead_code)", "cfg(feature = \"i18n\")"]` - puts
+//!   the given attributes, written without `#[]`, on the generated module as an
+//!   escape hatch for anything the macro has no option for
+//! - `const_len_array = true` - wraps every array constant in a newtype named
+//!   after its key, `pub const SOME: SomeArray = SomeArray(["none", "or",
+//!   "0"])`, which derefs to the `[&'static str; 3]` inside and can implement
+//!   your own traits
+//! - `validate_email_fields = ["contact.support_email"]` - fails the build when
+//!   a value at one of the given key paths is not an email address of the form
+//!   `local@domain.tld` without whitespace
+//! - `watch = true` - adds the absolute path of every file the expansion read
+//!   (the locale, its `$include`s and sidecars) to
+//!   `$OUT_DIR/anylang_inputs.txt`, one per line. A `build.rs` can turn them
+//!   into `cargo:rerun-if-changed` lines; it runs before the macro, so it sees
+//!   the files of the previous build:
+//!
+//!   ```ignore
+//!   fn main() {
+//!       let out_dir = std::env::var("OUT_DIR").unwrap();
+//!       let path = format!("{out_dir}/anylang_inputs.txt");
+//!       let inputs = std::fs::read_to_string(path).unwrap_or_default();
+//!
+//!       for input in inputs.lines() {
+//!           println!("cargo:rerun-if-changed={input}");
+//!       }
+//!   }
+//!   ```
+//! - `skip_leading_comments = true` - ignores `//` comment lines, such as a `//
+//!   ja_JP` signature, and a UTF-8 BOM before the JSON; error positions still
+//!   count the skipped lines
+//! - `cstring_mode = true` - emits string constants as `pub const PING: &CStr =
+//!   c"pong";` for C FFI, failing the build on a value with a NUL byte. Arrays
+//!   stay `&str`, generated helpers such as the `Locale` trait still return
+//!   `&str`, and `string_interning_mode` is ignored
+//! - `segment_by_namespace = true` - writes every top-level object to its own
+//!   file in `$OUT_DIR` (`lang_en_us_dummy.rs`, `lang_en_us_rust.rs`, ...) and
+//!   `include!`s it, keeping very large locales manageable. Needs a `build.rs`
+//!   so that Cargo sets `OUT_DIR`; use it in a single call per locale since the
+//!   file names only depend on the locale and the key
+//! - `key_length_limit = 64` - fails the build on a key longer than the given
+//!   number of characters, which would become an unwieldy identifier
+//! - `graphql_schema = "schema/locale.graphql"` - writes a GraphQL object type
+//!   named after the locale with a `lowerCamelCase` field per constant (`type
+//!   LangEnUs { ping: String! dummySome: [String!]! }`) for serving the strings
+//!   through `async-graphql` or `juniper`
+//! - `fallback_to_key = true` - defines every key that another locale of the
+//!   directory in the same format has and this one lacks, with its dotted path
+//!   as the value (`pub const GREETING: &str = "greeting";`), so missing
+//!   translations stand out during development
+//! - `partial = true` - loads a locale still being translated: every key that
+//!   another locale of the directory in the same format has and this one lacks
+//!   is defined as `""`, with a compile warning per key, instead of
+//!   `strict_structure` failing the build. Combined with `fallback_to_key`, the
+//!   dotted path is the value instead
+//! - `warn_string_len = 80` - emits a warning, without failing the build, for
+//!   every string longer than the given number of characters, suggesting a
+//!   review for brevity
+//! - `generate_rust_json = "target/locale_parsed.json"` - writes the parsed
+//!   locale back as pretty-printed JSON, every value a string or an array of
+//!   strings as in the generated constants, to check what the macro made of the
+//!   input
+//! - `fallback = "de_DE"`, `final_fallback = "en_US"` - fills every key missing
+//!   from the requested locale with the value of the first locale of the chain
+//!   that has it, read from the same directory, so every constant has a value.
+//!   Combined with `required_keys`, a key missing from all of them fails the
+//!   build
+//! - `lowercase_module_names = true` - lowercases the names of the modules
+//!   generated for objects, so `"Menu": { ... }` becomes `pub mod menu`
+//! - `xcode_strings = "Resources/en.lproj/Localizable.strings"` - writes an
+//!   Apple `.strings` file with a `"key" = "value";` line per key path (array
+//!   items as `key.0`, `key.1`, ...) so iOS and macOS apps can share the locale
+//!   files
+//! - `android_xml = "app/src/main/res/values/strings.xml"` - writes an Android
+//!   string resource file, the key path joined with `_` as the name (`<string
+//!   name="dummy_foo">`) and arrays as `<string-array>`, escaped the way `aapt`
+//!   expects
+//! - `pot_file = "locale/messages.pot"` - writes a gettext template in the
+//!   usual gettext style, the current value as the `msgid` and an empty
+//!   `msgstr`, the key paths of a value kept as a `#.` comment. Unlike
+//!   `gettext_pot`, a value repeated under several keys gets a single entry
+//! - `key_collision_strategy = "error" | "first_wins" | "last_wins"` - what to
+//!   do with a key repeated within one JSON object or YAML mapping, which the
+//!   JSON spec allows and parsers handle differently. `"error"`, the default,
+//!   fails the build naming the key and its position, `"first_wins"` keeps the
+//!   first value and `"last_wins"` the last one. Applies to `$include`d files
+//!   and to the mappings of a YAML root sequence merged into one as well. TOML
+//!   never allows a repeated key, so its files only take `"error"`
+//! - `swift_enum = "Sources/Lang/Locale.swift"` - writes a Swift `enum Lang`
+//!   mirroring the generated modules, an `UpperCamelCase` enum per object and a
+//!   `lowerCamelCase` `static let` per constant (`enum Dummy { static let foo:
+//!   String = "buzz" }`), so a Swift app can share the locale files with its
+//!   Rust core
+//! - `kotlin_object = "app/src/main/kotlin/Locale.kt"` - writes a Kotlin
+//!   `object Lang` mirroring the generated modules, an `UpperCamelCase` object
+//!   per object and a `const val` per string (`object Dummy { const val FOO =
+//!   "buzz" }`), arrays becoming `List<String>` properties, for Android UIs
+//!   sharing the locale files with a Rust core
+//! - `macro_visibility = "pub(crate)"` - visibility of the generated modules
+//!   and constants instead of `pub`: `"pub(crate)"`, `"pub(super)"`, any other
+//!   `pub(in ...)` or `"private"`. Restricted visibilities are relative to the
+//!   module they are written in, so the root module gets the given one and the
+//!   items inside it `pub(crate)`, reachable wherever the root module is
+//! - `auto_detect = true` - parses a locale file whose extension is none of
+//!   `.json`, `.rs`, `.toml`, `.yaml` and `.yml`, or which has none, by its
+//!   content: a file starting with `{` or `[` is read as JSON, one starting
+//!   with `---` as YAML (with the `yaml` feature), and anything else fails the
+//!   build
+//! - `key_filter_regex = r"^ui\."` *(`regex` feature)* - only keeps the keys
+//!   whose dotted path matches the regular expression, skipping the others and
+//!   the objects left without keys, for crates that need one section of a
+//!   shared locale file
+//! - `key_exclude_regex = "^_debug"` *(`regex` feature)* - drops the keys whose
+//!   dotted path matches the regular expression. Combined with
+//!   `key_filter_regex`, a key is kept when it matches the filter and not the
+//!   exclusion
+//! - `markdown_doc = "docs/locale_reference.md"` - writes a Markdown table with
+//!   a row per key path of the locale, its type and a column per locale with
+//!   the value in the locale and in every other locale of the directory in the
+//!   same format, left empty where one lacks the key
+//! - `transliterate = true` *(`transliterate` feature)* - emits an ASCII copy
+//!   of every constant next to it, other scripts romanized with `deunicode`
+//!   (`pub const PING_TRANSLITERATED: &str = "pong";` for `"понг"`), for
+//!   accessibility tools that need ASCII-safe strings
+//! - `max_nesting_depth = 3` - fails the build on an object nested deeper than
+//!   the given number of modules, naming its key path (`Key path 'a.b.c.d' in
+//!   en_US.json exceeds max_nesting_depth (3)`), to keep locale hierarchies
+//!   flat
+//! - `c_header = "include/locale.h"` - writes a C header with a `#define` per
+//!   constant named after its module path (`#define LANG_DUMMY_FOO "buzz"`),
+//!   arrays as brace lists for initializers (`static const char *some[] =
+//!   LANG_DUMMY_SOME;`), behind an include guard named after the file
+//! - `lua_table = "scripts/locale.lua"` - writes a Lua module returning a table
+//!   that mirrors the locale with its keys as written (`local Lang = { ping =
+//!   "pong", dummy = { foo = "buzz" } }`), keywords and other keys that are no
+//!   Lua names written as `["true"]`, for games scripted in Lua around a Rust
+//!   core
+//! - `ron_output = "target/locale.ron"` *(`ron` feature)* - writes the parsed
+//!   locale as pretty-printed RON, like `generate_rust_json`, for round-trip
+//!   tests in Rust
+//! - `locale_alias = {"en-GB": "en_US", "en-AU": "en_US"}` - includes the file
+//!   of another locale code when the requested one is an alias, before the
+//!   directory is searched, so codes with no file of their own can share one
+//! - `suppress_type_annotations = true` - leaves the `'static` out of the types
+//!   of the constants (`pub const PING: &str = "pong";`), as Rust infers it for
+//!   constants anyway. Arrays wrapped by `const_len_array` keep it, as the
+//!   field of the newtype needs it
+//! - `locale_metadata_key = "_locale"` - reads the object under the given
+//!   top-level key into the `meta` module described under Locale Metadata
+//!   rather than constants
+//! - `python_dict = "scripts/locale.py"` - writes a Python module defining a
+//!   `LANG` dict that mirrors the locale (`LANG = {"ping": "pong", "dummy":
+//!   {"foo": "buzz"}}`), for Python build scripts and data pipelines sharing
+//!   the locale files
+//! - `verify_rtl_markers = true` *(`bidi` feature)* - in Arabic, Hebrew,
+//!   Persian and other right-to-left locales, emits a warning for every string
+//!   mixing left-to-right and right-to-left text without a directional mark
+//!   (RLM, LRM, ALM) or isolate, which renderers may lay out in the wrong order
+//!
 //! ## Roadmap
 //!
 //! - [x] JSON support
-//! - [ ] TOML support
-//! - [ ] YAML support
+//! - [x] TOML support
+//! - [x] YAML support
 //!
 //! ## License
 //!
 //! **MIT**
 
-mod parser;
 #[macro_use]
 mod r#macro;
 
+use anylang_parse::{FileSystem, Options, RealFileSystem, parser};
 use proc_macro::TokenStream;
-use std::fs;
+use std::path;
 use syn::{LitStr, Token, parse::Parse, parse_macro_input};
 
 pub(crate) const CRATE_NAME: &str = "anylang";
@@ -199,11 +736,18 @@ pub(crate) const CRATE_NAME: &str = "anylang";
 struct MacroArgs {
     dir_path: LitStr,
     lang: LitStr,
+    options: Options,
 }
 
 impl Parse for MacroArgs {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let dir_path = input.parse::<LitStr>()?;
+        let dir_path = if input.peek(LitStr) {
+            input.parse::<LitStr>()?
+        } else {
+            let mac = input.parse::<syn::Macro>()?;
+
+            LitStr::new(&expand_str_macro(&mac)?, mac.bang_token.span)
+        };
 
         let lang = if input.peek(Token![,]) {
             let _comma: Token![,] = input.parse()?;
@@ -217,10 +761,132 @@ impl Parse for MacroArgs {
             ));
         };
 
-        Ok(Self { dir_path, lang })
+        let options = input.parse::<Options>()?;
+
+        Ok(Self {
+            dir_path,
+            lang,
+            options,
+        })
     }
 }
 
+/// Evaluates the `concat!` and `env!` calls allowed in place of the directory
+/// literal, so that `concat!(env!("OUT_DIR"), "/lang")` works
+fn expand_str_macro(mac: &syn::Macro) -> syn::Result<String> {
+    let args =
+        mac.parse_body_with(syn::punctuated::Punctuated::<syn::Expr, Token![,]>::parse_terminated)?;
+
+    if mac.path.is_ident("concat") {
+        let mut value = String::new();
+
+        for arg in args {
+            match arg {
+                syn::Expr::Lit(syn::ExprLit { lit, .. }) => {
+                    match lit {
+                        syn::Lit::Str(lit) => value.push_str(&lit.value()),
+                        syn::Lit::Char(lit) => value.push(lit.value()),
+                        syn::Lit::Bool(lit) => value.push_str(&lit.value.to_string()),
+                        syn::Lit::Int(lit) => value.push_str(lit.base10_digits()),
+                        syn::Lit::Float(lit) => value.push_str(lit.base10_digits()),
+                        lit => {
+                            return Err(syn::Error::new_spanned(
+                                lit,
+                                error!("Unsupported literal in concat!"),
+                            ));
+                        },
+                    }
+                },
+                syn::Expr::Macro(inner) => value.push_str(&expand_str_macro(&inner.mac)?),
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        error!("Expected a literal, concat! or env! in concat!"),
+                    ));
+                },
+            }
+        }
+
+        Ok(value)
+    } else if mac.path.is_ident("env") {
+        let Some(syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(name),
+            ..
+        })) = args.first()
+        else {
+            return Err(syn::Error::new_spanned(
+                mac,
+                error!("Expected an environment variable name in env!"),
+            ));
+        };
+
+        std::env::var(name.value()).map_err(|_| {
+            syn::Error::new_spanned(
+                name,
+                error!(format!(
+                    "Environment variable {} is not defined at compile time",
+                    name.value()
+                )),
+            )
+        })
+    } else {
+        Err(syn::Error::new_spanned(
+            &mac.path,
+            error!("Expected a string literal, concat! or env!"),
+        ))
+    }
+}
+
+/// RFC 4647 lookup fallbacks for `tag`, most specific first and with `_` as
+/// the separator: `zh-Hant-TW`, `zh_Hant`, `zh`
+fn lookup_candidates(tag: &str) -> Vec<String> {
+    let mut subtags = tag.split(['_', '-']).collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+
+    while !subtags.is_empty() {
+        candidates.push(subtags.join("_"));
+        subtags.pop();
+
+        // A singleton only introduces the subtag that was just removed
+        if subtags.last().is_some_and(|subtag| subtag.len() == 1) {
+            subtags.pop();
+        }
+    }
+
+    candidates
+}
+
+/// Whether `tag` is a `language[_Script][_REGION][_variant]` BCP 47 tag in
+/// canonical case, separated by `_` or `-`
+fn is_bcp47(tag: &str) -> bool {
+    let mut subtags = tag.split(['_', '-']).peekable();
+    let is = |subtag: &str, len: std::ops::RangeInclusive<usize>, f: fn(&u8) -> bool| {
+        len.contains(&subtag.len()) && subtag.as_bytes().iter().all(f)
+    };
+
+    if !subtags
+        .next()
+        .is_some_and(|language| is(language, 2..=3, u8::is_ascii_lowercase))
+    {
+        return false;
+    }
+
+    subtags.next_if(|script| {
+        is(script, 4..=4, u8::is_ascii_alphabetic)
+            && script.as_bytes()[0].is_ascii_uppercase()
+            && is(&script[1..], 3..=3, u8::is_ascii_lowercase)
+    });
+    subtags.next_if(|region| {
+        is(region, 2..=2, u8::is_ascii_uppercase) || is(region, 3..=3, u8::is_ascii_digit)
+    });
+
+    subtags.all(|variant| {
+        is(variant, 5..=8, u8::is_ascii_alphanumeric)
+            || (is(variant, 4..=4, u8::is_ascii_alphanumeric)
+                && variant.as_bytes()[0].is_ascii_digit())
+    })
+}
+
 impl MacroArgs {
     fn dir_path(&self) -> String {
         self.dir_path.value()
@@ -268,11 +934,126 @@ impl MacroArgs {
 #[cfg(feature = "json")]
 #[proc_macro]
 pub fn include_json_dir(input: TokenStream) -> TokenStream {
-    let args = parse_macro_input!(input as MacroArgs);
+    include_dir(parse_macro_input!(input as MacroArgs))
+}
+
+/// **Example of usage:**
+///
+/// ```toml
+/// ping = "понг"
+///
+/// [dummy]
+/// foo = "базз"
+/// some = ["ничего", "или", 0]
+///
+/// [rust]
+/// rust = "раст"
+/// released = 2015-05-15
+///
+/// [rust.good]
+/// true = [1, true]
+/// ```
+///
+/// ```ignore
+/// use anylang::include_toml_dir;
+///
+/// // Include Russian translations
+/// include_toml_dir!("./lang", "ru_RU");
+///
+/// fn main() {
+///     assert_eq!(lang::PING, "понг");
+///     assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+///     assert_eq!(lang::rust::RELEASED, "2015-05-15");
+///     assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+/// }
+/// ```
+///
+/// Takes the same options as [`include_json_dir!`], preferring `.toml` files
+/// over others of the same name unless `locale_priority` says otherwise.
+#[cfg(feature = "toml")]
+#[proc_macro]
+pub fn include_toml_dir(input: TokenStream) -> TokenStream {
+    let mut args = parse_macro_input!(input as MacroArgs);
+
+    if args.options.locale_priority.is_empty() {
+        args.options.locale_priority = vec!["toml".to_owned()];
+    }
+
+    include_dir(args)
+}
+
+/// **Example of usage:**
+///
+/// ```yaml
+/// ping: понг
+/// dummy: &dummy
+///   foo: базз
+///   some: [ничего, или, 0]
+/// copy: *dummy
+/// rust:
+///   rust: раст
+///   good:
+///     true: [1, true]
+/// ```
+///
+/// ```ignore
+/// use anylang::include_yaml_dir;
+///
+/// // Include Russian translations
+/// include_yaml_dir!("./lang", "ru_RU");
+///
+/// fn main() {
+///     assert_eq!(lang::PING, "понг");
+///     assert_eq!(lang::copy::SOME, ["ничего", "или", "0"]);
+///     assert_eq!(lang::rust::RUST, "раст");
+///     assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+/// }
+/// ```
+///
+/// Takes the same options as [`include_json_dir!`], preferring `.yaml` and
+/// then `.yml` files over others of the same name unless `locale_priority`
+/// says otherwise. Anchors, aliases and `<<` merge keys are resolved, and a
+/// root sequence of mappings is merged into one module, like a root array in
+/// JSON, unless `kv_array_mode` reads it as `key`/`value` pairs.
+#[cfg(feature = "yaml")]
+#[proc_macro]
+pub fn include_yaml_dir(input: TokenStream) -> TokenStream {
+    let mut args = parse_macro_input!(input as MacroArgs);
+
+    if args.options.locale_priority.is_empty() {
+        args.options.locale_priority = vec!["yaml".to_owned(), "yml".to_owned()];
+    }
+
+    include_dir(args)
+}
+
+/// Finds the file of the requested locale in the directory and generates its
+/// module
+#[cfg(feature = "json")]
+fn include_dir(args: MacroArgs) -> TokenStream {
     let dir_path = args.dir_path();
     let lang = args.lang();
 
-    let dir_entries = match fs::read_dir(&dir_path) {
+    if args.options.validate_bcp47 && !is_bcp47(&lang) {
+        return syn::Error::new_spanned(
+            &args.lang,
+            error!(format!(
+                "{lang} is not a BCP 47 language tag such as en_US, en-US or zh_Hant_TW"
+            )),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    // An alias names the file to use outright, before any negotiation
+    let lang = args
+        .options
+        .locale_alias
+        .iter()
+        .find(|(alias, _)| *alias == lang)
+        .map_or(lang, |(_, target)| target.clone());
+
+    let paths = match RealFileSystem.read_dir(path::Path::new(&dir_path)) {
         Ok(entries) => entries,
         Err(e) => {
             return syn::Error::new_spanned(
@@ -285,17 +1066,65 @@ pub fn include_json_dir(input: TokenStream) -> TokenStream {
     };
 
     let mut needed_file = None;
+    let candidates = if args.options.locale_negotiation {
+        lookup_candidates(&lang)
+    } else {
+        vec![lang.clone()]
+    };
+
+    let priority = args.options.locale_priority();
+
+    for candidate in &candidates {
+        // `file_stem` keeps dotted locale codes intact: `en.UK.json` is `en.UK`
+        let path = paths
+            .iter()
+            .filter(|path| {
+                path.file_stem().is_some_and(|file_name| {
+                    if args.options.locale_negotiation {
+                        file_name.to_string_lossy().replace('-', "_") == *candidate
+                    } else {
+                        file_name == candidate.as_str()
+                    }
+                })
+            })
+            // Extensions missing from `locale_priority` come last, by name so
+            // that the pick does not depend on the directory order
+            .min_by_key(|path| {
+                let rank = path.extension().and_then(|extension| {
+                    priority
+                        .iter()
+                        .position(|preferred| extension == preferred.as_str())
+                });
+
+                (rank.unwrap_or(usize::MAX), *path)
+            });
+
+        if let Some(path) = path {
+            match parser::parse_from_file(path.clone(), &args.options) {
+                Ok(mut file) => {
+                    if (args.options.fallback_to_key || args.options.partial)
+                        && let Err(e) =
+                            parser::fill_missing_keys(&RealFileSystem, &mut file, path, &paths)
+                    {
+                        return e.to_compile_error().into();
+                    }
 
-    for entry in dir_entries.flatten() {
-        let path = entry.path();
+                    if let Err(e) = parser::write_markdown_doc(&RealFileSystem, &file, path, &paths)
+                    {
+                        return e.to_compile_error().into();
+                    }
 
-        if let Some(file_name) = path.file_prefix()
-            && file_name == lang.as_str()
-        {
-            match parser::parse_from_file(path) {
-                Ok(file) => needed_file = Some(file),
+                    needed_file = Some(file);
+                },
                 Err(e) => return e.to_compile_error().into(),
             }
+
+            if args.options.strict_structure
+                && let Err(e) =
+                    parser::check_structure(&RealFileSystem, path, &paths, &args.options)
+            {
+                return e.to_compile_error().into();
+            }
             break;
         }
     }
@@ -305,11 +1134,47 @@ pub fn include_json_dir(input: TokenStream) -> TokenStream {
     } else {
         syn::Error::new_spanned(
             LitStr::new(&lang, proc_macro2::Span::call_site()),
-            error!(format!(
-                "Failed to get file with name {lang} in directory {dir_path}"
-            )),
+            error!(if candidates.len() > 1 {
+                format!(
+                    "Failed to get file with any of the names {} in directory {dir_path}",
+                    candidates.join(", ")
+                )
+            } else {
+                format!("Failed to get file with name {lang} in directory {dir_path}")
+            }),
         )
         .into_compile_error()
         .into()
     }
 }
+
+/// Includes a locale written by hand as a Rust module, e.g.
+/// `pub mod lang { pub const PING: &str = "pong"; }`, with `include!`
+/// semantics. The path is relative to the crate root like the directory of
+/// `include_json_dir!`, which picks up such `.rs` files as well.
+///
+/// ```ignore
+/// use anylang::include_rs_locale;
+///
+/// include_rs_locale!("./lang/en_US.rs");
+///
+/// fn main() {
+///     assert_eq!(lang::PING, "pong");
+/// }
+/// ```
+#[cfg(feature = "json")]
+#[proc_macro]
+pub fn include_rs_locale(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    if !path.value().ends_with(".rs") {
+        return syn::Error::new_spanned(&path, error!("Expected a path to a .rs file"))
+            .to_compile_error()
+            .into();
+    }
+
+    match parser::parse_from_file(path.value().into(), &Options::default()) {
+        Ok(file) => Into::<proc_macro2::TokenStream>::into(file).into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
@@ -0,0 +1,3 @@
+pub mod lang {
+    pub const SOURCE: &str = "rs";
+}
@@ -0,0 +1,7 @@
+pub mod lang {
+    pub const PING: &str = "pong";
+
+    pub mod dummy {
+        pub const FOO: &str = "buzz";
+    }
+}
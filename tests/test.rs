@@ -22,6 +22,673 @@ mod fr_fr {
     anylang::include_json_dir!("./tests/lang", "fr_FR");
 }
 
+mod ru_ru_file_name {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", append_file_name = true);
+}
+
+mod it_it {
+    anylang::include_json_dir!("./tests/lang", "it_IT");
+}
+
+mod it_ch {
+    anylang::include_json_dir!("./tests/lang", "it_CH");
+}
+
+mod en_au {
+    anylang::include_json_dir!("./tests/lang", "en_AU", pluralize_suffix = true);
+}
+
+mod es_es {
+    anylang::include_json_dir!("./tests/lang", "es_ES", kv_array_mode = true);
+}
+
+mod ru_ru_no_alloc {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", no_alloc = true);
+}
+
+mod ru_ru_trait {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", generate_trait = true);
+}
+
+mod en_dot_uk {
+    anylang::include_json_dir!("./tests/lang", "en.UK");
+}
+
+mod pt_dot_br {
+    anylang::include_json_dir!("./tests/lang", "pt.BR");
+}
+
+mod ru_ru_wasm {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", wasm_export = true);
+}
+
+mod ru_ru_null_empty {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", null_behavior = "empty");
+}
+
+mod de_de_null_error {
+    anylang::include_json_dir!("./tests/lang", "de_DE", null_behavior = "error");
+}
+
+mod en_nz_uppercase {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", transform = "uppercase");
+}
+
+mod en_nz_lowercase {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", transform = "lowercase");
+}
+
+mod en_nz_trim {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", transform = "trim");
+}
+
+mod en_nz_title_case {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", transform = "title_case");
+}
+
+mod en_ie_screaming_snake {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "screaming_snake");
+}
+
+mod en_ie_screaming_kebab {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "screaming_kebab");
+}
+
+mod en_ie_lower_snake {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "lower_snake");
+}
+
+mod en_ie_lower_camel {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "lower_camel");
+}
+
+mod en_ie_upper_camel {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "upper_camel");
+}
+
+mod en_ie_kebab {
+    anylang::include_json_dir!("./tests/lang", "en_IE", name_transform = "kebab");
+}
+
+mod en_au_tests {
+    anylang::include_json_dir!("./tests/lang", "en_AU", generate_tests = true);
+}
+
+mod en_ca_escape_html {
+    anylang::include_json_dir!("./tests/lang", "en_CA", escape_html = true);
+}
+
+#[cfg(feature = "checksum")]
+mod de_de_checksum {
+    anylang::include_json_dir!("./tests/lang", "de_DE", checksum_file = true);
+}
+
+mod en_gb {
+    anylang::include_json_dir!("./tests/lang", "en_GB");
+}
+
+mod en_nz_strict_arrays {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", strict_typed_arrays = true);
+}
+
+mod ru_ru_compat {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        compat_aliases = "ru_RU.compat.json"
+    );
+}
+
+mod ru_ru_enum {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", generate_enum = true);
+}
+
+mod en_au_min_len {
+    anylang::include_json_dir!("./tests/lang", "en_AU", min_string_len = 3);
+}
+
+mod en_za {
+    anylang::include_json_dir!("./tests/lang", "en_ZA", generate_enum = true);
+}
+
+mod en_us_schema {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        json_schema_export = "target/anylang-test/en_US.schema.json"
+    );
+}
+
+mod en_nz_truncate {
+    anylang::include_json_dir!("./tests/lang", "en_NZ", truncate_at = 5);
+}
+
+mod en_in {
+    anylang::include_json_dir!("./tests/lang", "en_IN");
+}
+
+mod en_in_csv {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_IN",
+        csv_report = "target/anylang-test/en_IN.csv"
+    );
+}
+
+mod en_in_pot {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_IN",
+        gettext_pot = "target/anylang-test/en_IN.pot"
+    );
+}
+
+mod en_in_strings {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_IN",
+        xcode_strings = "target/anylang-test/en_IN.strings"
+    );
+}
+
+mod en_gh {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_GH",
+        android_xml = "target/anylang-test/en_GH.xml"
+    );
+}
+
+mod en_us_concat {
+    anylang::include_json_dir!(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/", "lang"),
+        "en_US"
+    );
+}
+
+mod en_sg {
+    anylang::include_json_dir!("./tests/lang", "en_SG", deduplicate_strings = true);
+}
+
+mod en_sg_pot {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_SG",
+        pot_file = "target/anylang-test/en_SG.pot"
+    );
+}
+
+mod en_ph {
+    anylang::include_json_dir!("./tests/lang", "en_PH", generate_enum = true);
+}
+
+mod en_us_versions {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        deprecate_since = "2.0.0",
+        generate_trait = true
+    );
+}
+
+mod ru_ru_typescript {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        typescript_types = "target/anylang-test/ru_RU.d.ts"
+    );
+}
+
+mod en_tt_typescript {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_TT",
+        lowercase_module_names = true,
+        name_transform = "lower_camel",
+        typescript_types = "target/anylang-test/en_TT.d.ts"
+    );
+}
+
+#[cfg(feature = "base64")]
+mod en_us_base64 {
+    anylang::include_json_dir!("./tests/lang/base64", "en_US", encoding = "base64");
+}
+
+mod en_us_bcp47 {
+    anylang::include_json_dir!("./tests/lang", "en_US", validate_bcp47 = true);
+}
+
+// The stored hash outlives changes to the generated code, ignore the warning
+#[allow(deprecated)]
+mod en_us_hash {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        stable_hash = "target/anylang-test/en_US.hash"
+    );
+}
+
+mod it_it_negotiated {
+    anylang::include_json_dir!("./tests/lang", "it-IT-x-custom", locale_negotiation = true);
+}
+
+mod ru_ru_interned {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", string_interning_mode = true);
+}
+
+mod en_nz_no_trailing_spaces {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_NZ",
+        transform = "trim",
+        validate_no_trailing_spaces = true
+    );
+}
+
+mod en_us_priority_default {
+    anylang::include_json_dir!("./tests/lang/priority", "en_US");
+}
+
+mod en_us_priority_rs {
+    anylang::include_json_dir!(
+        "./tests/lang/priority",
+        "en_US",
+        locale_priority = ["rs", "json"]
+    );
+}
+
+mod ru_ru_required {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        required_keys = ["ping", "dummy.foo", "rust.good.true", "rust.good"]
+    );
+}
+
+#[cfg(feature = "normalization")]
+mod en_mt_normalization {
+    anylang::include_json_dir!("./tests/lang", "en_MT", normalization_file = true);
+}
+
+// The warning about the key `挨拶` is the point of this fixture
+#[allow(deprecated)]
+mod ja_jp_non_ascii_keys {
+    anylang::include_json_dir!("./tests/lang", "ja_JP", warn_non_ascii_keys = true);
+}
+
+mod en_us_rs {
+    anylang::include_rs_locale!("./tests/lang/rust/en_US.rs");
+}
+
+mod en_us_rs_dir {
+    anylang::include_json_dir!("./tests/lang/rust", "en_US", locale_priority = ["rs"]);
+}
+
+mod en_bz_sanitize_html {
+    anylang::include_json_dir!("./tests/lang", "en_BZ", sanitize_html = true);
+}
+
+mod en_jm {
+    anylang::include_json_dir!("./tests/lang", "en_JM");
+}
+
+mod en_us_strict_structure {
+    anylang::include_json_dir!("./tests/lang/strict", "en_US", strict_structure = true);
+}
+
+mod en_jm_const_ptr {
+    anylang::include_json_dir!("./tests/lang", "en_JM", const_ptr = true);
+}
+
+mod en_jm_module_attrs {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_JM",
+        module_attrs = ["cfg(test)", "doc = \"Jamaican English\""]
+    );
+}
+
+mod en_us_const_len_array {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        const_len_array = true,
+        generate_trait = true
+    );
+}
+
+mod en_ke_validate_email_fields {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_KE",
+        validate_email_fields = ["contact.support_email"]
+    );
+}
+
+mod ja_jp_skip_leading_comments {
+    anylang::include_json_dir!(
+        "./tests/lang/comments",
+        "ja_JP",
+        skip_leading_comments = true
+    );
+}
+
+mod en_us_cstring_mode {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        cstring_mode = true,
+        const_ptr = true,
+        generate_trait = true
+    );
+}
+
+macro_rules! include_locale {
+    ($dir:expr, $lang:literal) => {
+        anylang::include_json_dir!($dir, $lang);
+    };
+}
+
+mod en_us_macro_rules {
+    include_locale!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/lang"), "en_US");
+}
+
+mod en_us_key_length_limit {
+    anylang::include_json_dir!("./tests/lang", "en_US", key_length_limit = 5);
+}
+
+mod ru_ru_graphql_schema {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        graphql_schema = "target/anylang-test/ru_RU.graphql"
+    );
+}
+
+mod fr_fr_fallback_to_key {
+    anylang::include_json_dir!("./tests/lang/fallback", "fr_FR", fallback_to_key = true);
+}
+
+// The warnings about the untranslated keys are the point of this fixture
+#[allow(deprecated)]
+mod fr_fr_partial {
+    anylang::include_json_dir!("./tests/lang/fallback", "fr_FR", partial = true);
+}
+
+#[allow(deprecated)]
+mod fr_fr_partial_strict_structure {
+    anylang::include_json_dir!(
+        "./tests/lang/fallback",
+        "fr_FR",
+        partial = true,
+        strict_structure = true
+    );
+}
+
+mod sw_ke_frontmatter {
+    anylang::include_json_dir!("./tests/lang/frontmatter", "sw_KE");
+}
+
+// The warnings about the long strings are the point of this fixture
+#[allow(deprecated)]
+mod en_bz_warn_string_len {
+    anylang::include_json_dir!("./tests/lang", "en_BZ", warn_string_len = 16);
+}
+
+mod ru_ru_generate_rust_json {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        generate_rust_json = "target/anylang-test/ru_RU.parsed.json"
+    );
+}
+
+mod de_at_fallback {
+    anylang::include_json_dir!(
+        "./tests/lang/chain",
+        "de_AT",
+        fallback = "de_DE",
+        final_fallback = "en_US"
+    );
+}
+
+mod en_tt_lowercase_module_names {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_TT",
+        lowercase_module_names = true,
+        generate_trait = true
+    );
+}
+
+mod duplicate_first {
+    anylang::include_json_dir!(
+        "./tests/lang/duplicate",
+        "en_US",
+        key_collision_strategy = "first_wins"
+    );
+}
+
+mod duplicate_last {
+    anylang::include_json_dir!(
+        "./tests/lang/duplicate",
+        "en_US",
+        key_collision_strategy = "last_wins"
+    );
+}
+
+#[cfg(feature = "yaml")]
+mod en_us_yaml_kv_array_mode {
+    anylang::include_yaml_dir!("./tests/lang/yaml/kv", "en_US", kv_array_mode = true);
+}
+
+mod en_us_swift {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        swift_enum = "target/anylang-test/en_US.swift"
+    );
+}
+
+mod en_us_kotlin {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        kotlin_object = "target/anylang-test/en_US.kt"
+    );
+}
+
+mod en_us_pub_crate {
+    anylang::include_json_dir!("./tests/lang", "en_US", macro_visibility = "pub(crate)");
+}
+
+mod en_us_private {
+    anylang::include_json_dir!("./tests/lang", "en_US", macro_visibility = "private");
+
+    pub fn ping() -> &'static str {
+        lang::PING
+    }
+}
+
+mod detect_txt {
+    anylang::include_json_dir!("./tests/lang/detect", "en_US", auto_detect = true);
+}
+
+mod detect_no_extension {
+    anylang::include_json_dir!("./tests/lang/detect", "de_DE", auto_detect = true);
+}
+
+#[cfg(feature = "regex")]
+mod ru_ru_filter {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        key_filter_regex = r"^rust\.(rust|good)",
+        generate_enum = true
+    );
+}
+
+#[cfg(feature = "regex")]
+mod ru_ru_exclude {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        key_filter_regex = r"^rust\.",
+        key_exclude_regex = r"^rust\.good\.",
+        generate_enum = true
+    );
+}
+
+mod en_us_markdown {
+    anylang::include_json_dir!(
+        "./tests/lang/fallback",
+        "en_US",
+        markdown_doc = "target/anylang-test/locale_reference.md"
+    );
+}
+
+#[cfg(feature = "transliterate")]
+mod ru_ru_transliterate {
+    anylang::include_json_dir!("./tests/lang", "ru_RU", transliterate = true);
+}
+
+mod en_us_nesting {
+    anylang::include_json_dir!("./tests/lang", "en_US", max_nesting_depth = 2);
+}
+
+mod ru_ru_c_header {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "ru_RU",
+        c_header = "target/anylang-test/locale.h"
+    );
+}
+
+mod en_us_lua {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        lua_table = "target/anylang-test/locale.lua"
+    );
+}
+
+mod en_tt_lua {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_TT",
+        lua_table = "target/anylang-test/en_TT.lua"
+    );
+}
+
+#[cfg(feature = "ron")]
+mod en_us_ron {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        ron_output = "target/anylang-test/en_US.ron"
+    );
+}
+
+mod en_au_alias {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en-AU",
+        locale_alias = {"en-GB": "en_US", "en-AU": "en_US"}
+    );
+}
+
+mod en_us_elided {
+    anylang::include_json_dir!("./tests/lang", "en_US", suppress_type_annotations = true);
+}
+
+mod en_us_elided_arrays {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_US",
+        suppress_type_annotations = true,
+        const_len_array = true
+    );
+}
+
+mod en_us_metadata_key {
+    anylang::include_json_dir!(
+        "./tests/lang/metadata",
+        "en_US",
+        locale_metadata_key = "_locale"
+    );
+}
+
+mod en_in_python {
+    anylang::include_json_dir!(
+        "./tests/lang",
+        "en_IN",
+        python_dict = "target/anylang-test/en_IN.py"
+    );
+}
+
+#[cfg(feature = "toml")]
+mod ru_ru_toml {
+    anylang::include_toml_dir!("./tests/lang/toml", "ru_RU");
+}
+
+// The warning about the unmarked version string is the point of this fixture
+#[cfg(feature = "bidi")]
+#[allow(deprecated)]
+mod he_il_verify_rtl_markers {
+    anylang::include_json_dir!("./tests/lang", "he_IL", verify_rtl_markers = true);
+}
+
+#[cfg(feature = "yaml")]
+mod ru_ru_yaml {
+    anylang::include_yaml_dir!("./tests/lang/yaml", "ru_RU");
+}
+
+#[cfg(feature = "toml")]
+mod ru_ru_toml_graphql_schema {
+    anylang::include_toml_dir!(
+        "./tests/lang/toml",
+        "ru_RU",
+        graphql_schema = "target/anylang-test/ru_RU_toml.graphql"
+    );
+}
+
+#[cfg(feature = "toml")]
+mod en_us_toml_fallback_to_key {
+    anylang::include_toml_dir!("./tests/lang/toml", "en_US", fallback_to_key = true);
+}
+
+#[cfg(feature = "yaml")]
+mod en_us_priority_yaml {
+    anylang::include_yaml_dir!("./tests/lang/priority", "en_US");
+}
+
+#[cfg(feature = "yaml")]
+mod en_us_priority_json_over_yaml {
+    anylang::include_yaml_dir!(
+        "./tests/lang/priority",
+        "en_US",
+        locale_priority = ["json", "yaml"]
+    );
+}
+
+#[cfg(feature = "yaml")]
+mod duplicate_yaml_first {
+    anylang::include_yaml_dir!(
+        "./tests/lang/yaml/duplicate",
+        "en_US",
+        key_collision_strategy = "first_wins"
+    );
+}
+
+#[cfg(feature = "yaml")]
+mod duplicate_yaml_last {
+    anylang::include_yaml_dir!(
+        "./tests/lang/yaml/duplicate",
+        "en_US",
+        key_collision_strategy = "last_wins"
+    );
+}
+
 #[test]
 fn check_obj() {
     use crate::ru_ru::*;
@@ -73,3 +740,1162 @@ fn check_bool() {
 
     assert_eq!(lang::FR_FR, "false");
 }
+
+#[test]
+fn check_append_file_name() {
+    use crate::ru_ru_file_name::*;
+
+    assert_eq!(lang::RU_RU__PING, "понг");
+    assert_eq!(lang::dummy::RU_RU__FOO, "базз");
+    assert_eq!(lang::rust::good::RU_RU__TRUE, ["1", "true"]);
+}
+
+#[test]
+fn check_key_order() {
+    assert_eq!(it_it::lang::PING, it_ch::lang::PING);
+    assert_eq!(it_it::lang::ZETA, it_ch::lang::ZETA);
+    assert_eq!(it_it::lang::dummy::FOO, it_ch::lang::dummy::FOO);
+    assert_eq!(it_it::lang::dummy::SOME, it_ch::lang::dummy::SOME);
+}
+
+#[test]
+fn check_pluralize_suffix() {
+    use crate::en_au::*;
+
+    assert_eq!(lang::items_plural(0), "no items");
+    assert_eq!(lang::items_plural(1), "one item");
+    assert_eq!(lang::items_plural(5), "many items");
+    assert_eq!(lang::cart::apples_plural(0), "apples");
+    assert_eq!(lang::cart::apples_plural(1), "an apple");
+    assert_eq!(lang::ITEMS_ONE, "one item");
+}
+
+#[test]
+fn check_kv_array_mode() {
+    use crate::es_es::*;
+
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::dummy::FOO, "buzz");
+    assert_eq!(lang::SOME, ["none", "or", "0"]);
+    assert!(lang::IS.is_empty());
+}
+
+#[test]
+fn check_no_alloc() {
+    use crate::ru_ru_no_alloc::*;
+
+    const PING_LEN: usize = lang::ping().len();
+
+    assert_eq!(PING_LEN, "понг".len());
+    assert_eq!(lang::ping(), "понг");
+    assert_eq!(lang::dummy::foo(), "базз");
+    assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+    assert_eq!(lang::rust::rust(), "раст");
+    assert!(lang::rust::is().is_empty());
+}
+
+#[test]
+fn check_generate_trait() {
+    use crate::ru_ru_trait::lang::{Locale, StaticLocale};
+
+    struct MockLocale;
+
+    impl Locale for MockLocale {
+        fn ping(&self) -> &str {
+            "mock"
+        }
+
+        fn dummy_foo(&self) -> &str {
+            StaticLocale.dummy_foo()
+        }
+
+        fn dummy_some(&self) -> &[&str] {
+            &[]
+        }
+
+        fn rust_good_true(&self) -> &[&str] {
+            &[]
+        }
+
+        fn rust_is(&self) -> &str {
+            ""
+        }
+
+        fn rust_rust(&self) -> &str {
+            ""
+        }
+    }
+
+    fn greet(locale: &impl Locale) -> &str {
+        locale.ping()
+    }
+
+    assert_eq!(greet(&StaticLocale), "понг");
+    assert_eq!(greet(&MockLocale), "mock");
+    assert_eq!(MockLocale.dummy_foo(), "базз");
+    assert_eq!(StaticLocale.dummy_some(), ["ничего", "или", "0"]);
+    assert_eq!(StaticLocale.rust_good_true(), ["1", "true"]);
+}
+
+#[test]
+fn check_dotted_file_name() {
+    assert_eq!(en_dot_uk::lang::COLOUR, "colour");
+    assert_eq!(pt_dot_br::lang::PT_BR, "olá");
+    assert!(en_uk::lang::EN_UK.is_empty());
+}
+
+#[test]
+fn check_wasm_export() {
+    use crate::ru_ru_wasm::*;
+
+    assert_eq!(lang::PING, "понг");
+    assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+}
+
+#[test]
+fn check_null_behavior() {
+    assert!(ru_ru_null_empty::lang::rust::IS.is_empty());
+    assert_eq!(de_de_null_error::lang::DE_DE, "228.01");
+}
+
+#[test]
+fn check_transform() {
+    assert_eq!(en_nz_uppercase::lang::GREETING, "  HELLO WORLD  ");
+    assert_eq!(en_nz_uppercase::lang::LIST, ["ONE TWO", "THREE"]);
+    assert_eq!(en_nz_lowercase::lang::GREETING, "  hello world  ");
+    assert_eq!(en_nz_trim::lang::GREETING, "hello wORLD");
+    assert_eq!(en_nz_title_case::lang::GREETING, "  Hello World  ");
+    assert_eq!(en_nz_title_case::lang::LIST, ["One Two", "Three"]);
+}
+
+#[test]
+fn check_name_transform() {
+    assert_eq!(en_ie_screaming_snake::lang::FOO_BAR, "camel");
+    assert_eq!(en_ie_screaming_snake::lang::nested::BTN_OK, "kebab");
+    assert_eq!(en_ie_screaming_kebab::lang::FOO__BAR, "camel");
+    assert_eq!(en_ie_screaming_kebab::lang::SOME__KEY, "snake");
+    assert_eq!(en_ie_lower_snake::lang::foo_bar, "camel");
+    assert_eq!(en_ie_lower_snake::lang::nested::btn_ok, "kebab");
+    assert_eq!(en_ie_lower_camel::lang::fooBar, "camel");
+    assert_eq!(en_ie_lower_camel::lang::someKey, "snake");
+    assert_eq!(en_ie_upper_camel::lang::FooBar, "camel");
+    assert_eq!(en_ie_upper_camel::lang::nested::BtnOk, "kebab");
+    assert_eq!(en_ie_kebab::lang::some__key, "snake");
+}
+
+#[test]
+fn check_escape_html() {
+    use crate::en_ca_escape_html::*;
+
+    assert_eq!(
+        lang::MARKUP,
+        "&lt;b&gt;Tom &amp; &quot;Jerry&#x27;s&quot;&lt;/b&gt;"
+    );
+    assert_eq!(lang::PLAIN, "nothing to escape");
+}
+
+#[cfg(feature = "checksum")]
+#[test]
+fn check_checksum_file() {
+    assert_eq!(de_de_checksum::lang::DE_DE, "228.01");
+}
+
+#[test]
+fn check_include() {
+    use crate::en_gb::*;
+
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::CANCEL, "Cancel");
+    assert_eq!(lang::dummy::FOO, "buzz");
+    assert_eq!(lang::extra::FOO, "buzz");
+    assert_eq!(lang::extra::BAR, "override");
+}
+
+#[test]
+fn check_strict_typed_arrays() {
+    assert_eq!(en_nz_strict_arrays::lang::LIST, ["one two", "three"]);
+}
+
+#[test]
+#[allow(deprecated)]
+fn check_compat_aliases() {
+    use crate::ru_ru_compat::*;
+
+    assert_eq!(lang::PINGOLD, lang::PING);
+    assert_eq!(lang::dummy::BAR, "базз");
+}
+
+#[test]
+fn check_generate_enum() {
+    use crate::ru_ru_enum::lang::LangKeys;
+
+    assert_eq!(LangKeys::Ping.as_str(), "ping");
+    assert_eq!(LangKeys::DummyFoo.as_str(), "dummy.foo");
+    assert_eq!(LangKeys::RustGoodTrue.as_str(), "rust.good.true");
+    assert_eq!(LangKeys::all().len(), 6);
+    assert!(LangKeys::all().contains(&LangKeys::RustIs));
+}
+
+#[test]
+fn check_min_string_len() {
+    assert_eq!(en_au_min_len::lang::ITEMS_ONE, "one item");
+}
+
+#[test]
+fn check_cfg_blocks() {
+    use crate::en_za::*;
+
+    if cfg!(target_os = "ios") {
+        assert_eq!(lang::BACK_BUTTON, "iOS back");
+    } else if cfg!(unix) {
+        assert_eq!(lang::BACK_BUTTON, "Back");
+    } else {
+        assert_eq!(lang::BACK_BUTTON, "←");
+    }
+
+    if cfg!(target_os = "windows") {
+        assert_eq!(lang::menu::TITLE, "Windows menu");
+    } else {
+        assert_eq!(lang::menu::TITLE, "Menu");
+    }
+
+    #[cfg(unix)]
+    assert_eq!(lang::UNIX_ONLY, "yes");
+
+    assert_eq!(lang::LangKeys::all().len(), 2);
+}
+
+#[test]
+fn check_json_schema_export() {
+    assert_eq!(en_us_schema::lang::FOO, "buzz");
+
+    let schema = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_US.schema.json"
+    ))
+    .unwrap();
+
+    assert!(schema.contains("\"$schema\": \"https://json-schema.org/draft/2020-12/schema\""));
+    assert!(schema.contains(
+        "\"required\": [\n    \"dummy\",\n    \"foo\",\n    \"ping\",\n    \"rust\"\n  ]"
+    ));
+    assert!(schema.contains("\"minItems\": 3"));
+}
+
+#[test]
+fn check_truncate_at() {
+    assert_eq!(en_nz_truncate::lang::GREETING, "  he…");
+    assert_eq!(en_nz_truncate::lang::LIST, ["one …", "three"]);
+}
+
+#[test]
+fn check_crlf_normalization() {
+    assert_eq!(en_in::lang::ADDRESS, "1 Main Street\nMumbai");
+    assert_eq!(en_in::lang::LINES, ["a\nb", "c\rd"]);
+}
+
+#[test]
+fn check_csv_report() {
+    assert_eq!(en_in_csv::lang::ADDRESS, "1 Main Street\nMumbai");
+
+    let csv = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_IN.csv"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        csv,
+        "key,value\r\naddress,\"1 Main \
+         Street\nMumbai\"\r\nlines.0,\"a\nb\"\r\nlines.1,\"c\rd\"\r\n"
+    );
+}
+
+#[test]
+fn check_gettext_pot() {
+    assert_eq!(en_in_pot::lang::LINES, ["a\nb", "c\rd"]);
+
+    let pot = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_IN.pot"
+    ))
+    .unwrap();
+
+    assert!(
+        pot.starts_with(
+            "msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n"
+        )
+    );
+    assert!(pot.contains(
+        "\n#. 1 Main Street\n#. Mumbai\n#: en_IN.json\nmsgid \"address\"\nmsgstr \"\"\n"
+    ));
+    assert!(pot.contains("\nmsgid \"lines.1\"\nmsgstr \"\"\n"));
+}
+
+#[test]
+fn check_pot_file() {
+    assert_eq!(en_sg_pot::lang::CONFIRM, "OK");
+
+    let pot = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_SG.pot"
+    ))
+    .unwrap();
+
+    assert!(pot.starts_with("msgid \"\"\nmsgstr \"\"\n"));
+    assert!(pot.ends_with(
+        "\n#. cancel, dialog.close, dismiss\n#: en_SG.json\nmsgid \"Cancel\"\nmsgstr \"\"\n\n#. \
+         confirm, dialog.ok\n#: en_SG.json\nmsgid \"OK\"\nmsgstr \"\"\n"
+    ));
+}
+
+#[test]
+fn check_xcode_strings() {
+    assert_eq!(en_in_strings::lang::ADDRESS, "1 Main Street\nMumbai");
+
+    let strings = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_IN.strings"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        strings,
+        r#""address" = "1 Main Street\nMumbai";
+"lines.0" = "a\nb";
+"lines.1" = "c\rd";
+"#
+    );
+}
+
+#[test]
+fn check_android_xml() {
+    assert_eq!(en_gh::lang::dummy::HANDLE, "@home");
+
+    let xml = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_GH.xml"
+    ))
+    .unwrap();
+
+    assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<resources>\n"));
+    assert!(xml.contains("    <string name=\"ping\">pong</string>\n"));
+    assert!(xml.contains(
+        r#"    <string name="dummy_foo">Tom &amp; Jerry\'s &lt;b&gt;\"show\"&lt;/b&gt;</string>"#
+    ));
+    assert!(xml.contains(r#"    <string name="dummy_handle">\@home</string>"#));
+    assert!(
+        xml.contains(
+            "    <string-array name=\"days\">\n        <item>Mon</item>\n        \
+             <item>Tue</item>\n    </string-array>\n"
+        )
+    );
+    assert!(xml.ends_with("</resources>\n"));
+}
+
+#[test]
+fn check_concat_dir_path() {
+    assert_eq!(en_us_concat::lang::PING, "pong");
+}
+
+#[test]
+fn check_deduplicate_strings() {
+    use crate::en_sg::*;
+
+    assert_eq!(lang::CANCEL, "Cancel");
+    assert_eq!(lang::DISMISS, "Cancel");
+    assert_eq!(lang::dialog::CLOSE, "Cancel");
+    assert_eq!(lang::dialog::OK, "OK");
+    assert_eq!(lang::CONFIRM, "OK");
+}
+
+#[test]
+fn check_context_annotation() {
+    use crate::en_ph::*;
+
+    assert_eq!(lang::BACK, "Back");
+    assert_eq!(lang::settings::TITLE, "Settings");
+    assert_eq!(lang::LangKeys::all().len(), 2);
+}
+
+#[test]
+#[allow(deprecated)]
+fn check_deprecate_since() {
+    use crate::en_us_versions::*;
+
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::dummy::SOME, ["none", "or", "0"]);
+    assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+}
+
+#[test]
+fn check_typescript_types() {
+    assert_eq!(ru_ru_typescript::lang::PING, "понг");
+
+    let dts = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/ru_RU.d.ts"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        dts,
+        "export namespace dummy {
+    export const FOO: string;
+    export const SOME: readonly [string, string, string];
+}
+export const PING: string;
+export namespace rust {
+    export namespace good {
+        export const TRUE: readonly [string, string];
+    }
+    export const IS: string;
+    export const RUST: string;
+}
+"
+    );
+
+    // Declared under the names of the generated modules and constants
+    assert_eq!(en_tt_typescript::lang::menu::recent::clear, "Clear recent");
+
+    let dts = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_TT.d.ts"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        dts,
+        "export namespace menu {
+    export namespace recent {
+        export const clear: string;
+    }
+    export const save: string;
+}
+"
+    );
+}
+
+#[cfg(feature = "base64")]
+#[test]
+fn check_base64_encoding() {
+    assert_eq!(en_us_base64::lang::PING, "pong");
+    assert_eq!(en_us_base64::lang::GREETING, "Grüß Gott");
+}
+
+#[test]
+fn check_validate_bcp47() {
+    assert_eq!(en_us_bcp47::lang::PING, "pong");
+}
+
+#[test]
+fn check_stable_hash() {
+    assert_eq!(en_us_hash::lang::PING, "pong");
+
+    let hash = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_US.hash"
+    ))
+    .unwrap();
+
+    assert_eq!(hash.len(), 16);
+    assert!(hash.bytes().all(|byte| byte.is_ascii_hexdigit()));
+}
+
+#[test]
+fn check_locale_negotiation() {
+    assert_eq!(it_it_negotiated::lang::ZETA, it_it::lang::ZETA);
+    assert_eq!(it_it_negotiated::lang::dummy::FOO, "buzz");
+}
+
+#[test]
+fn check_string_interning_mode() {
+    use crate::ru_ru_interned::*;
+
+    assert_eq!(lang::PING, "понг");
+    assert_eq!(lang::dummy::FOO, "базз");
+    assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+    assert!(lang::rust::IS.is_empty());
+    assert_eq!(lang::rust::RUST, "раст");
+}
+
+#[test]
+fn check_validate_no_trailing_spaces() {
+    assert_eq!(en_nz_no_trailing_spaces::lang::GREETING, "hello wORLD");
+}
+
+#[test]
+fn check_locale_priority() {
+    assert_eq!(en_us_priority_default::lang::SOURCE, "json");
+    assert_eq!(en_us_priority_rs::lang::SOURCE, "rs");
+}
+
+#[test]
+fn check_required_keys() {
+    assert_eq!(ru_ru_required::lang::dummy::FOO, "базз");
+}
+
+#[cfg(feature = "normalization")]
+#[test]
+fn check_normalization_file() {
+    use crate::en_mt_normalization::*;
+
+    assert_eq!(lang::LIGATURE, "file");
+    assert_eq!(lang::ACCENT, "\u{e9}");
+    assert_eq!(lang::UNTOUCHED, "\u{fb01}");
+}
+
+#[test]
+fn check_warn_non_ascii_keys() {
+    use crate::ja_jp_non_ascii_keys::*;
+
+    assert_eq!(lang::挨拶, "こんにちは");
+    assert_eq!(lang::PING, "pong");
+}
+
+#[test]
+fn check_rs_locale() {
+    assert_eq!(en_us_rs::lang::PING, "pong");
+    assert_eq!(en_us_rs::lang::dummy::FOO, "buzz");
+    assert_eq!(en_us_rs_dir::lang::PING, "pong");
+    assert_eq!(en_us_rs_dir::lang::dummy::FOO, "buzz");
+}
+
+#[test]
+fn check_sanitize_html() {
+    use crate::en_bz_sanitize_html::*;
+
+    assert_eq!(lang::WELCOME, "Hello, World!");
+    assert_eq!(lang::COMMENT, "Plain text");
+    assert_eq!(lang::MATH, "1 < 2 and 3 > 2");
+}
+
+#[test]
+fn check_locale_info() {
+    use crate::en_jm::*;
+
+    assert_eq!(lang::GREETING, "Wah gwaan");
+    assert_eq!(lang::meta::LANGUAGE, "English");
+    assert_eq!(lang::meta::REGION, "Jamaica");
+    assert_eq!(lang::meta::TEXT_DIRECTION, "ltr");
+    assert_eq!(lang::meta::PLURAL_RULES, "n == 1 ? 'one' : 'other'");
+}
+
+#[test]
+fn check_strict_structure() {
+    use crate::en_us_strict_structure::*;
+
+    assert_eq!(lang::TITLE, "Settings");
+    assert_eq!(lang::menu::OPTIONS, ["Light", "Dark"]);
+}
+
+#[test]
+fn check_const_ptr() {
+    use crate::en_jm_const_ptr::*;
+
+    let greeting = unsafe {
+        std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+            lang::GREETING_PTR,
+            lang::GREETING_LEN,
+        ))
+    };
+
+    assert_eq!(greeting, "Wah gwaan");
+}
+
+#[test]
+fn check_module_attrs() {
+    assert_eq!(en_jm_module_attrs::lang::GREETING, "Wah gwaan");
+}
+
+#[test]
+fn check_const_len_array() {
+    use crate::en_us_const_len_array::lang::{self, Locale};
+
+    let some: [&'static str; 3] = lang::dummy::SOME.0;
+
+    assert_eq!(some, ["none", "or", "0"]);
+    assert_eq!(lang::dummy::SOME.len(), 3);
+    assert_eq!(
+        lang::dummy::SOME,
+        lang::dummy::SomeArray(["none", "or", "0"])
+    );
+    assert_eq!(lang::StaticLocale.dummy_some(), ["none", "or", "0"]);
+}
+
+#[test]
+fn check_validate_email_fields() {
+    use crate::en_ke_validate_email_fields::*;
+
+    assert_eq!(lang::contact::SUPPORT_EMAIL, "support@example.co.ke");
+}
+
+#[test]
+fn check_skip_leading_comments() {
+    assert_eq!(ja_jp_skip_leading_comments::lang::PING, "ポン");
+}
+
+#[test]
+fn check_cstring_mode() {
+    use crate::en_us_cstring_mode::lang::{self, Locale};
+
+    let ping: &'static std::ffi::CStr = lang::PING;
+
+    assert_eq!(ping, c"pong");
+    assert_eq!(lang::PING_LEN, 4);
+    assert_eq!(lang::dummy::SOME, ["none", "or", "0"]);
+    assert_eq!(lang::StaticLocale.ping(), "pong");
+}
+
+#[test]
+fn check_macro_rules_arguments() {
+    assert_eq!(en_us_macro_rules::lang::PING, "pong");
+}
+
+#[test]
+fn check_key_length_limit() {
+    assert_eq!(en_us_key_length_limit::lang::PING, "pong");
+}
+
+#[test]
+fn check_graphql_schema() {
+    assert_eq!(ru_ru_graphql_schema::lang::PING, "понг");
+
+    let schema = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/ru_RU.graphql"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        schema,
+        "type LangRuRu {
+  dummyFoo: String!
+  dummySome: [String!]!
+  ping: String!
+  rustGoodTrue: [String!]!
+  rustIs: String!
+  rustRust: String!
+}
+"
+    );
+}
+
+#[test]
+fn check_fallback_to_key() {
+    use crate::fr_fr_fallback_to_key::*;
+
+    assert_eq!(lang::menu::SAVE, "Enregistrer");
+    assert_eq!(lang::menu::QUIT, "menu.quit");
+    assert_eq!(lang::GREETING, "greeting");
+    assert_eq!(lang::COLORS, ["colors", "colors"]);
+    assert_eq!(lang::settings::TITLE, "settings.title");
+}
+
+#[test]
+fn check_partial() {
+    use crate::fr_fr_partial::*;
+
+    assert_eq!(lang::menu::SAVE, "Enregistrer");
+    assert_eq!(lang::menu::QUIT, "");
+    assert_eq!(lang::GREETING, "");
+    assert_eq!(lang::COLORS, ["", ""]);
+    assert_eq!(lang::settings::TITLE, "");
+    // Keys left to fill are no structure error
+    assert_eq!(fr_fr_partial_strict_structure::lang::menu::QUIT, "");
+}
+
+#[test]
+fn check_frontmatter() {
+    use crate::sw_ke_frontmatter::*;
+
+    assert_eq!(lang::HELLO, "Habari");
+    assert_eq!(lang::meta::DESCRIPTION, "Kenyan Swahili strings");
+    assert_eq!(lang::meta::AUTHOR, "Amina");
+    assert_eq!(lang::meta::VERSION, "1.2");
+}
+
+#[test]
+fn check_warn_string_len() {
+    use crate::en_bz_warn_string_len::*;
+
+    assert_eq!(lang::WELCOME, "Hello, <b>World</b>!");
+    assert_eq!(lang::MATH, "1 < 2 and 3 > 2");
+}
+
+#[test]
+fn check_generate_rust_json() {
+    assert_eq!(ru_ru_generate_rust_json::lang::PING, "понг");
+
+    let json = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/ru_RU.parsed.json"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        json,
+        r#"{
+  "dummy": {
+    "foo": "базз",
+    "some": [
+      "ничего",
+      "или",
+      "0"
+    ]
+  },
+  "ping": "понг",
+  "rust": {
+    "good": {
+      "true": [
+        "1",
+        "true"
+      ]
+    },
+    "is": "",
+    "rust": "раст"
+  }
+}"#
+    );
+}
+
+#[test]
+fn check_fallback_chain() {
+    use crate::de_at_fallback::*;
+
+    assert_eq!(lang::GREETING, "Servus");
+    assert_eq!(lang::menu::SAVE, "Sichern");
+    assert_eq!(lang::menu::QUIT, "Beenden");
+    assert_eq!(lang::menu::HELP, "Help");
+    assert_eq!(lang::JANUARY, "Januar");
+    assert_eq!(lang::COLORS, ["Red", "Green"]);
+}
+
+#[test]
+fn check_lowercase_module_names() {
+    use crate::en_tt_lowercase_module_names::lang::{self, Locale};
+
+    assert_eq!(lang::menu::SAVE, "Save");
+    assert_eq!(lang::menu::recent::CLEAR, "Clear recent");
+    assert_eq!(lang::StaticLocale.menu_recent_clear(), "Clear recent");
+}
+
+#[test]
+fn check_key_collision_strategy() {
+    assert_eq!(duplicate_first::lang::PING, "pong");
+    assert_eq!(duplicate_first::lang::dummy::FOO, "first");
+    assert_eq!(duplicate_last::lang::PING, "pang");
+    assert_eq!(duplicate_last::lang::dummy::FOO, "second");
+}
+
+#[test]
+fn check_swift_enum() {
+    assert_eq!(en_us_swift::lang::PING, "pong");
+
+    let swift = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_US.swift"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        swift,
+        r#"enum Lang {
+    enum Dummy {
+        static let some: [String] = ["none", "or", "0"]
+    }
+    static let foo: String = "buzz"
+    static let ping: String = "pong"
+    enum Rust {
+        enum Good {
+            static let `true`: [String] = ["1", "true"]
+        }
+        static let `is`: String = ""
+        static let rust: String = "rust"
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn check_kotlin_object() {
+    assert_eq!(en_us_kotlin::lang::FOO, "buzz");
+
+    let kotlin = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_US.kt"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        kotlin,
+        r#"object Lang {
+    object Dummy {
+        val SOME: List<String> = listOf("none", "or", "0")
+    }
+    const val FOO = "buzz"
+    const val PING = "pong"
+    object Rust {
+        object Good {
+            val TRUE: List<String> = listOf("1", "true")
+        }
+        const val IS = ""
+        const val RUST = "rust"
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn check_macro_visibility() {
+    assert_eq!(en_us_pub_crate::lang::PING, "pong");
+    assert_eq!(en_us_pub_crate::lang::dummy::SOME, ["none", "or", "0"]);
+    assert_eq!(en_us_private::ping(), "pong");
+}
+
+#[test]
+fn check_auto_detect() {
+    assert_eq!(detect_txt::lang::PING, "pong");
+    assert_eq!(detect_no_extension::lang::PING, "Pong");
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn check_key_filter_regex() {
+    use crate::ru_ru_filter::lang::{self, LangKeys};
+
+    assert_eq!(lang::rust::RUST, "раст");
+    assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+    assert_eq!(
+        LangKeys::all()
+            .iter()
+            .map(LangKeys::as_str)
+            .collect::<Vec<_>>(),
+        ["rust.good.true", "rust.rust"]
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn check_key_exclude_regex() {
+    use crate::ru_ru_exclude::lang::{self, LangKeys};
+
+    assert_eq!(lang::rust::RUST, "раст");
+    assert_eq!(
+        LangKeys::all()
+            .iter()
+            .map(LangKeys::as_str)
+            .collect::<Vec<_>>(),
+        ["rust.is", "rust.rust"]
+    );
+}
+
+#[test]
+fn check_markdown_doc() {
+    assert_eq!(en_us_markdown::lang::GREETING, "Hello");
+
+    let md = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/locale_reference.md"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        md,
+        r#"| Key Path | Type | en_US | fr_FR |
+|---|---|---|---|
+| `colors` | array | ["Red", "Green"] |  |
+| `greeting` | string | Hello |  |
+| `menu.quit` | string | Quit |  |
+| `menu.save` | string | Save | Enregistrer |
+| `settings.title` | string | Settings |  |
+"#
+    );
+}
+
+#[cfg(feature = "transliterate")]
+#[test]
+fn check_transliterate() {
+    use crate::ru_ru_transliterate::*;
+
+    assert_eq!(lang::PING, "понг");
+    assert_eq!(lang::PING_TRANSLITERATED, "pong");
+    assert_eq!(lang::dummy::SOME_TRANSLITERATED, ["nichego", "ili", "0"]);
+    assert_eq!(lang::rust::RUST_TRANSLITERATED, "rast");
+}
+
+#[test]
+fn check_max_nesting_depth() {
+    assert_eq!(en_us_nesting::lang::rust::good::TRUE, ["1", "true"]);
+}
+
+#[test]
+fn check_c_header() {
+    assert_eq!(ru_ru_c_header::lang::PING, "понг");
+
+    let header = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/locale.h"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        header,
+        r#"#ifndef LOCALE_H
+#define LOCALE_H
+
+#define LANG_DUMMY_FOO "базз"
+#define LANG_DUMMY_SOME { "ничего", "или", "0" }
+#define LANG_PING "понг"
+#define LANG_RUST_GOOD_TRUE { "1", "true" }
+#define LANG_RUST_IS ""
+#define LANG_RUST_RUST "раст"
+
+#endif /* LOCALE_H */
+"#
+    );
+}
+
+#[test]
+fn check_lua_table() {
+    assert_eq!(en_us_lua::lang::FOO, "buzz");
+
+    let lua = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/locale.lua"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        lua,
+        r#"local Lang = {
+    dummy = {
+        some = { "none", "or", "0" },
+    },
+    foo = "buzz",
+    ping = "pong",
+    rust = {
+        good = {
+            ["true"] = { "1", "true" },
+        },
+        is = "",
+        rust = "rust",
+    },
+}
+
+return Lang
+"#
+    );
+
+    // Keys keep their case, as Lua tells `Menu` and `menu` apart
+    assert_eq!(en_tt_lua::lang::Menu::SAVE, "Save");
+
+    let lua = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_TT.lua"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        lua,
+        r#"local Lang = {
+    Menu = {
+        Recent = {
+            Clear = "Clear recent",
+        },
+        Save = "Save",
+    },
+}
+
+return Lang
+"#
+    );
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn check_ron_output() {
+    assert_eq!(en_us_ron::lang::PING, "pong");
+
+    let ron = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_US.ron"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        ron,
+        r#"{
+    "dummy": {
+        "some": [
+            "none",
+            "or",
+            "0",
+        ],
+    },
+    "foo": "buzz",
+    "ping": "pong",
+    "rust": {
+        "good": {
+            "true": [
+                "1",
+                "true",
+            ],
+        },
+        "is": "",
+        "rust": "rust",
+    },
+}"#
+    );
+}
+
+#[test]
+fn check_locale_alias() {
+    // en_AU.json has no `ping`, the alias picks en_US.json
+    assert_eq!(en_au_alias::lang::PING, "pong");
+    assert_eq!(en_au_alias::lang::FOO, "buzz");
+}
+
+#[test]
+fn check_suppress_type_annotations() {
+    let ping: &'static str = en_us_elided::lang::PING;
+
+    assert_eq!(ping, "pong");
+    assert_eq!(en_us_elided::lang::dummy::SOME, ["none", "or", "0"]);
+    assert_eq!(*en_us_elided_arrays::lang::dummy::SOME, ["none", "or", "0"]);
+}
+
+#[test]
+fn check_locale_metadata_key() {
+    use crate::en_us_metadata_key::*;
+
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::meta::LANGUAGE, "English");
+    assert_eq!(lang::meta::TEXT_DIRECTION, "ltr");
+}
+
+#[test]
+fn check_python_dict() {
+    assert_eq!(en_in_python::lang::LINES, ["a\nb", "c\rd"]);
+
+    let py = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/en_IN.py"
+    ))
+    .unwrap();
+
+    assert_eq!(
+        py,
+        r#"LANG = {
+  "address": "1 Main Street\nMumbai",
+  "lines": [
+    "a\nb",
+    "c\rd"
+  ]
+}
+"#
+    );
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn check_toml() {
+    use crate::ru_ru_toml::*;
+
+    assert_eq!(lang::PING, "понг");
+    assert_eq!(lang::COUNT, "42");
+    assert_eq!(lang::RATIO, "0.5");
+    assert_eq!(lang::ENABLED, "true");
+    assert_eq!(lang::RELEASED, "2015-05-15");
+    assert_eq!(lang::UPDATED, "2024-01-02T03:04:05Z");
+    assert_eq!(lang::ALARM, "07:30:00");
+    assert_eq!(lang::dummy::FOO, "базз");
+    assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+    assert_eq!(lang::rust::RUST, "раст");
+    assert_eq!(lang::rust::good::TRUE, ["1", "true", "1.5"]);
+}
+
+#[cfg(feature = "bidi")]
+#[test]
+fn check_verify_rtl_markers() {
+    use crate::he_il_verify_rtl_markers::*;
+
+    assert_eq!(lang::PING, "פונג");
+    assert_eq!(lang::WELCOME, "ברוכים הבאים ל\u{2068}AnyLang\u{2069}!");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn check_yaml() {
+    use crate::ru_ru_yaml::*;
+
+    assert_eq!(lang::PING, "понг");
+    assert_eq!(lang::COUNT, "42");
+    assert_eq!(lang::RATIO, "0.5");
+    assert_eq!(lang::defaults::FOO, "базз");
+    assert_eq!(lang::dummy::FOO, "другое");
+    assert_eq!(lang::dummy::SOME, ["ничего", "или", "0"]);
+    assert_eq!(lang::copy::SOME, ["ничего", "или", "0"]);
+    assert_eq!(lang::rust::RUST, "раст");
+    assert_eq!(lang::rust::good::TRUE, ["1", "true"]);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn check_graphql_schema_toml() {
+    assert_eq!(ru_ru_toml_graphql_schema::lang::PING, "понг");
+
+    let schema = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/target/anylang-test/ru_RU_toml.graphql"
+    ))
+    .unwrap();
+
+    assert!(schema.starts_with("type LangRuRu {\n"), "{schema}");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn check_toml_shared_reading() {
+    use crate::en_us_toml_fallback_to_key::*;
+
+    // Frontmatter and `$include` work as in JSON
+    assert_eq!(lang::meta::AUTHOR, "Amina");
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::COUNT, "7");
+    assert_eq!(lang::dummy::FOO, "buzz");
+    // Filled in from ru_RU.toml, the other TOML locale of the directory
+    assert_eq!(lang::dummy::SOME, ["dummy.some"; 3]);
+    assert_eq!(lang::rust::RUST, "rust.rust");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn check_yaml_locale_priority() {
+    assert_eq!(en_us_priority_default::lang::SOURCE, "json");
+    assert_eq!(en_us_priority_yaml::lang::SOURCE, "yaml");
+    assert_eq!(en_us_priority_json_over_yaml::lang::SOURCE, "json");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn check_yaml_key_collision_strategy() {
+    assert_eq!(duplicate_yaml_first::lang::PING, "pong");
+    assert_eq!(duplicate_yaml_first::lang::dummy::FOO, "first");
+    assert_eq!(duplicate_yaml_last::lang::PING, "pang");
+    assert_eq!(duplicate_yaml_last::lang::dummy::FOO, "second");
+    // From the `$include`d file, whose `ping` the locale overrides
+    assert_eq!(duplicate_yaml_last::lang::COUNT, "7");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn check_yaml_kv_array_mode() {
+    use crate::en_us_yaml_kv_array_mode::*;
+
+    assert_eq!(lang::PING, "pong");
+    assert_eq!(lang::dummy::FOO, "buzz");
+    assert_eq!(lang::SOME, ["none", "or", "0"]);
+}